@@ -104,6 +104,76 @@ unsafe impl<T: UniqueType + ?Sized> UniqueType for &mut T {
     }
 }
 
+/// Capture the token of a [`UniqueType`] so it can be stored and checked later
+///
+/// This is just [`UniqueType::token`], but is useful when you only have access
+/// to an owner through a reference (for example, a `&mut T`), and want to store
+/// something cheap and [`Copy`] instead.
+///
+/// NOTE: re-validating a stored token with [`UniqueType::owns`] only guarantees that
+/// the token was created by the same owner if the owner is a [`UniqueToken`].
+/// Otherwise, [`UniqueType::owns`] may also accept tokens created by other values.
+/// See [`CapturedToken`] for a helper which enforces this.
+///
+/// ```
+/// use unique_types::{capture_token, runtime::RuntimeUt, UniqueType};
+///
+/// let mut owner = RuntimeUt::new();
+/// let borrowed = &mut owner;
+/// let token = capture_token(&borrowed);
+///
+/// assert!(owner.owns(&token));
+/// assert!(!RuntimeUt::new().owns(&token));
+/// ```
+#[inline]
+pub fn capture_token<U: ?Sized + UniqueType>(owner: &U) -> U::Token {
+    owner.token()
+}
+
+/// A token captured from an owner, which can be re-validated against an owner later
+///
+/// ```
+/// use unique_types::{runtime::RuntimeUt, CapturedToken};
+///
+/// let owner = RuntimeUt::new();
+/// let captured = CapturedToken::new(&owner);
+///
+/// assert!(captured.is_owned_by(&owner));
+/// assert!(!captured.is_owned_by(&RuntimeUt::new()));
+/// ```
+pub struct CapturedToken<O: ?Sized + UniqueToken> {
+    token: O::Token,
+}
+
+impl<O: ?Sized + UniqueToken> Copy for CapturedToken<O> {}
+impl<O: ?Sized + UniqueToken> Clone for CapturedToken<O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: ?Sized + UniqueToken> CapturedToken<O> {
+    /// Capture the token of the given owner
+    #[inline]
+    pub fn new(owner: &O) -> Self {
+        Self {
+            token: owner.token(),
+        }
+    }
+
+    /// Get the captured token
+    #[inline]
+    pub fn token(&self) -> O::Token {
+        self.token
+    }
+
+    /// Check if the owner is the same owner that this token was captured from
+    #[inline]
+    pub fn is_owned_by(&self, owner: &O) -> bool {
+        owner.owns(&self.token)
+    }
+}
+
 /// A type level boolean representing [`IsUnique`] `true`
 pub struct Yes;
 /// A type level boolean representing [`IsUnique`] `fasle`
@@ -40,6 +40,7 @@ impl LifetimeUt<'_> {
 
 impl LifetimeUtToken<'_> {
     /// Create a new [`LifetimeUtToken`]
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         crate::TrivialToken::NEW
     }
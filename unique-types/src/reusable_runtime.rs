@@ -79,6 +79,55 @@ impl<C: CounterRef> ReuseRuntimeUt<C> {
             value: C::with(Counter::next_value)?,
         })
     }
+
+    /// Replace the value of this [`ReuseRuntimeUt`] with a new one, and reclaim the old value
+    ///
+    /// Returns the token of the old value. After this, tokens created before the call are no
+    /// longer owned by this [`ReuseRuntimeUt`], so all handles (like indices or keys) checked
+    /// against it are invalidated. Since the old value is reclaimed, another
+    /// [`ReuseRuntimeUt`] may own the old tokens later, just like after dropping it.
+    ///
+    /// # Panics
+    ///
+    /// If the counter is exhausted
+    ///
+    /// ```
+    /// use unique_types::{reusable_runtime::ReuseRuntimeUt, UniqueType};
+    ///
+    /// let mut owner = ReuseRuntimeUt::new();
+    /// let token = owner.token();
+    ///
+    /// let old = owner.rebrand();
+    /// assert!(old == token);
+    /// assert!(!owner.owns(&token));
+    /// assert!(owner.owns(&owner.token()));
+    /// ```
+    pub fn rebrand(&mut self) -> ReuseRuntimeUtToken<C> {
+        self.try_rebrand()
+            .expect("Tried to rebrand a ReuseRuntimeUt from an exhausted counter")
+    }
+
+    /// Replace the value of this [`ReuseRuntimeUt`] with a new one, and reclaim the old value
+    ///
+    /// Returns None if the counter is exhausted, see [`ReuseRuntimeUt::rebrand`] for details
+    pub fn try_rebrand(&mut self) -> Option<ReuseRuntimeUtToken<C>> {
+        // get the new value before reclaiming the old one, so that they are distinct
+        let value = C::with(Counter::next_value)?;
+        let old = core::mem::replace(&mut self.value, value);
+
+        // SAFETY:
+        // * the old value will not be used by self anymore
+        // * This ReuseRuntimeUt owned the old value
+        // * C::with ensures that this is the same counter as in try_with_counter
+        C::with(|counter| unsafe {
+            let _ = counter.reclaim(old);
+        });
+
+        Some(ReuseRuntimeUtToken {
+            _ty_traits: PhantomData,
+            value: old,
+        })
+    }
 }
 
 // SAFETY: CounterRef and Counter guarantees that ...
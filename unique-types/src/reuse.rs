@@ -193,3 +193,82 @@ unsafe impl<T> Reuse for PhantomData<T> {
         None
     }
 }
+
+/// A fixed capacity ring buffer of values to reuse
+///
+/// [`Ring`] stores up to `CAP` reclaimed values without allocating, and will
+/// always extract the most recently reclaimed value first.
+///
+/// If a value is reclaimed while the ring is full, then the oldest value in the ring
+/// is discarded to make room for it. The discarded value will never be reused, since
+/// it is no longer tracked by the ring, and the base counter has already moved past it.
+/// So this bounds the memory used for reuse, at the cost of permanently losing some ids
+/// under heavy churn.
+///
+/// Like [`Vec`](alloc::vec::Vec), this only implements [`ReuseMut`], so it should be wrapped
+/// in a [`std::sync::Mutex`] or [`core::cell::RefCell`] to be used with [`custom_counter`]
+///
+/// ```
+/// use unique_types::{reusable_runtime::ReuseRuntimeUt, reuse::Ring, UniqueType};
+///
+/// unique_types::custom_counter! {
+///     struct MyCounter(core::num::NonZeroU8);
+///     with_reuse std::sync::Mutex<Ring<core::num::NonZeroU8, 1>>
+/// }
+///
+/// let a = ReuseRuntimeUt::<MyCounter>::with_counter();
+/// let b = ReuseRuntimeUt::<MyCounter>::with_counter();
+/// let c = ReuseRuntimeUt::<MyCounter>::with_counter();
+/// let (a_token, b_token) = (a.token(), b.token());
+///
+/// drop(a);
+/// drop(b);
+///
+/// // the ring only has space for one value, so a's value was discarded
+/// let d = ReuseRuntimeUt::<MyCounter>::with_counter();
+/// assert!(d.owns(&b_token));
+/// let e = ReuseRuntimeUt::<MyCounter>::with_counter();
+/// assert!(!e.owns(&a_token));
+/// ```
+pub struct Ring<T, const CAP: usize> {
+    values: [Option<T>; CAP],
+    start: usize,
+    len: usize,
+}
+
+// SAFETY: extract_mut only yields values that were written to the ring in reclaim_mut
+unsafe impl<T, const CAP: usize> ReuseMut for Ring<T, CAP> {
+    type Value = T;
+
+    const NEW: Self = Self {
+        values: [const { None }; CAP],
+        start: 0,
+        len: 0,
+    };
+
+    fn reclaim_mut(&mut self, value: Self::Value) -> Result<(), Self::Value> {
+        if CAP == 0 {
+            return Err(value);
+        }
+
+        if self.len == CAP {
+            // the ring is full, so overwrite the oldest value
+            self.values[self.start] = Some(value);
+            self.start = (self.start + 1) % CAP;
+        } else {
+            self.values[(self.start + self.len) % CAP] = Some(value);
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+
+    fn extract_mut(&mut self) -> Option<Self::Value> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        self.values[(self.start + self.len) % CAP].take()
+    }
+}
@@ -1,8 +1,10 @@
-use std::{hint::black_box, marker};
+use std::hint::black_box;
 
 use criterion::{criterion_group, Criterion};
 use rand::Rng;
-use ut_arena::{dense_slab::DenseSlab, generation::NoGeneration, slab::Slab};
+use ut_arena::{
+    dense_slab::DenseSlab, generation::NoGeneration, generic_sparse::GenericSparseArena, slab::Slab,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Action {
@@ -30,9 +32,9 @@ fn make_workload(rng: &mut impl Rng, config: WorkloadConfig) -> Vec<Action> {
     assert!(config.removals <= config.inserts);
 
     let mut pool = Vec::new();
-    pool.extend(std::iter::repeat(ActionType::Insert).take(config.inserts));
-    pool.extend(std::iter::repeat(ActionType::Remove).take(config.removals));
-    pool.extend(std::iter::repeat(ActionType::Access).take(config.accesses));
+    pool.extend(std::iter::repeat_n(ActionType::Insert, config.inserts));
+    pool.extend(std::iter::repeat_n(ActionType::Remove, config.removals));
+    pool.extend(std::iter::repeat_n(ActionType::Access, config.accesses));
     let mut pool_removed = Vec::new();
     let mut may_access = Vec::new();
 
@@ -96,6 +98,7 @@ fn make_workload(rng: &mut impl Rng, config: WorkloadConfig) -> Vec<Action> {
 fn run_sparse(c: &mut Criterion) {
     let mut bench_workload = move |name: &str, config: WorkloadConfig| {
         let workload = make_workload(&mut rand::thread_rng(), config);
+        let append_workload = make_append_workload(&workload);
 
         c.benchmark_group(name)
             .throughput(criterion::Throughput::Elements(workload.len() as u64))
@@ -105,9 +108,15 @@ fn run_sparse(c: &mut Criterion) {
             .bench_function("sparse", |b| {
                 b.iter(|| run_workload_sparse(&workload));
             })
+            .bench_function("sparse-append", |b| {
+                b.iter(|| run_workload_sparse_append(&append_workload));
+            })
             .bench_function("sparse-lt", |b| {
                 b.iter(|| run_workload_sparse_lt(&workload));
             })
+            .bench_function("sparse-lt-assume-filled", |b| {
+                b.iter(|| run_workload_sparse_lt_assume_filled(&workload));
+            })
             .bench_function("dense", |b| {
                 b.iter(|| run_workload_dense(&workload));
             });
@@ -167,6 +176,43 @@ fn run_workload_sparse(workload: &[Action]) {
     }
 }
 
+/// Convert a workload's keys (which assume slot reuse) to the keys
+/// that [`GenericSparseArena::push_value`] would yield (which never reuses slots)
+fn make_append_workload(workload: &[Action]) -> Vec<Action> {
+    let mut slab = Slab::new();
+    let mut next_key = 0;
+
+    workload
+        .iter()
+        .map(|&action| match action {
+            Action::Insert(c) => {
+                slab.insert(next_key);
+                next_key += 1;
+                Action::Insert(c)
+            }
+            Action::Remove(key) => Action::Remove(slab.remove(key)),
+            Action::Access(key) => Action::Access(slab[key]),
+        })
+        .collect()
+}
+
+fn run_workload_sparse_append(workload: &[Action]) {
+    let mut arena = GenericSparseArena::<_, _, NoGeneration>::new();
+    for &action in workload {
+        match action {
+            Action::Insert(c) => {
+                arena.push_value::<usize>(c);
+            }
+            Action::Remove(key) => {
+                arena.remove(key);
+            }
+            Action::Access(key) => {
+                black_box(arena[key]);
+            }
+        }
+    }
+}
+
 fn run_workload_dense(workload: &[Action]) {
     let mut slab = DenseSlab::new();
     for &action in workload {
@@ -202,7 +248,6 @@ fn run_workload_slab(workload: &[Action]) {
 }
 
 fn run_workload_sparse_lt(workload: &[Action]) {
-    use unique_types::lifetime::LifetimeUt;
     unique_types::unique_lifetime!(lt);
     let mut slab =
         ut_arena::generic_sparse::GenericSparseArena::<_, _, NoGeneration>::with_owner(lt);
@@ -223,8 +268,66 @@ fn run_workload_sparse_lt(workload: &[Action]) {
     }
 }
 
+fn run_workload_sparse_lt_assume_filled(workload: &[Action]) {
+    unique_types::unique_lifetime!(lt);
+    let mut slab =
+        ut_arena::generic_sparse::GenericSparseArena::<_, _, NoGeneration>::with_owner(lt);
+    for &action in workload {
+        match action {
+            Action::Insert(c) => {
+                slab.insert::<usize>(c);
+            }
+            Action::Remove(key) => {
+                let key = unsafe { ut_vec::UtIndex::new_unchecked(key, slab.owner()) };
+                slab.remove(key);
+            }
+            Action::Access(key) => {
+                let key = unsafe { ut_vec::UtIndex::new_unchecked(key, slab.owner()) };
+                black_box(unsafe { *slab.get_assume_filled(key) });
+            }
+        }
+    }
+}
+
+/// Append values to an arena which has many empty slots, so the free list has to be moved
+/// past each new slot
+fn run_append_after_removes(c: &mut Criterion) {
+    const LEN: usize = 1024;
+
+    let mut arena = GenericSparseArena::<_, _, NoGeneration>::new();
+    for i in 0..LEN {
+        let _: usize = arena.insert(i);
+    }
+    for i in (0..LEN).step_by(2) {
+        arena.remove(i);
+    }
+
+    c.benchmark_group("append-after-removes")
+        .throughput(criterion::Throughput::Elements(LEN as u64))
+        .bench_function("sparse-append", |b| {
+            b.iter_batched_ref(
+                || arena.clone(),
+                |arena| {
+                    for i in 0..LEN {
+                        black_box(arena.push_value::<usize>(i));
+                    }
+                },
+                criterion::BatchSize::SmallInput,
+            );
+        })
+        .bench_function("sparse-append-reserved", |b| {
+            b.iter(|| {
+                let mut arena = GenericSparseArena::<_, _, NoGeneration>::with_capacity(LEN);
+                for i in 0..LEN {
+                    black_box(arena.push_value::<usize>(i));
+                }
+                arena
+            });
+        });
+}
+
 criterion_group! {
-    bench_workloads, run_sparse
+    bench_workloads, run_sparse, run_append_after_removes
 }
 
 criterion::criterion_main! { bench_workloads }
@@ -0,0 +1,109 @@
+//! [`proptest`] support for fuzzing code that uses arenas
+//!
+//! This provides [`Arbitrary`] for [`ArenaKey`](crate::key::ArenaKey), and strategies which build
+//! arenas from random sequences of insertions and removals, so the arenas have
+//! realistic free lists and generations.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use ut_arena::arbitrary::arb_slab;
+//!
+//! proptest!(|(slab in arb_slab::<u8>())| {
+//!     prop_assert_eq!(slab.iter().count(), slab.len());
+//! });
+//! ```
+
+use alloc::vec::Vec;
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection, prop_oneof,
+    sample::Index,
+    strategy::Strategy,
+};
+
+use crate::{generation::Generation, slab::Slab, slotmap::SlotMap};
+
+/// The maximum number of times a slot is emptied before generating an arbitrary key
+pub(crate) const MAX_GENERATION_CYCLES: u32 = 64;
+
+/// The maximum number of insertions and removals used to build an arbitrary arena
+const MAX_OPS: usize = 256;
+
+/// Get the filled generation of a slot which has been emptied `cycles` times
+///
+/// This stops early if the generation is exhausted
+pub(crate) fn filled_generation<G: Generation>(cycles: u32) -> G::Filled {
+    // SAFETY: EMPTY is guaranteed to be empty
+    let mut generation = unsafe { G::EMPTY.fill() };
+
+    for _ in 0..cycles {
+        // SAFETY: generation was just filled
+        match unsafe { generation.try_empty() } {
+            // SAFETY: try_empty returns an empty generation
+            Ok(empty) => generation = unsafe { empty.fill() },
+            Err(_) => break,
+        }
+    }
+
+    // SAFETY: generation was just filled
+    unsafe { generation.to_filled() }
+}
+
+#[derive(Debug)]
+enum Op<T> {
+    Insert(T),
+    Remove(Index),
+}
+
+fn arb_ops<T: Arbitrary>() -> impl Strategy<Value = Vec<Op<T>>> {
+    collection::vec(
+        prop_oneof![
+            any::<T>().prop_map(Op::Insert),
+            any::<Index>().prop_map(Op::Remove),
+        ],
+        0..MAX_OPS,
+    )
+}
+
+/// A strategy which builds a [`Slab`] from a random sequence of insertions and removals
+pub fn arb_slab<T: Arbitrary>() -> impl Strategy<Value = Slab<T>> {
+    arb_ops::<T>().prop_map(|ops| {
+        let mut slab = Slab::new();
+        let mut keys = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Insert(value) => keys.push(slab.insert(value)),
+                Op::Remove(index) if !keys.is_empty() => {
+                    let key = keys.swap_remove(index.index(keys.len()));
+                    slab.remove(key);
+                }
+                Op::Remove(_) => (),
+            }
+        }
+
+        slab
+    })
+}
+
+/// A strategy which builds a [`SlotMap`] from a random sequence of insertions and removals
+pub fn arb_slotmap<T: Arbitrary>() -> impl Strategy<Value = SlotMap<T>> {
+    arb_ops::<T>().prop_map(|ops| {
+        let mut slotmap = SlotMap::new();
+        let mut keys = Vec::new();
+
+        for op in ops {
+            match op {
+                Op::Insert(value) => keys.push(slotmap.insert(value)),
+                Op::Remove(index) if !keys.is_empty() => {
+                    let key = keys.swap_remove(index.index(keys.len()));
+                    slotmap.remove(key);
+                }
+                Op::Remove(_) => (),
+            }
+        }
+
+        slotmap
+    })
+}
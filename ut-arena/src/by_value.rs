@@ -0,0 +1,156 @@
+//! An arena which can also look up keys by value
+//!
+//! see [`ByValueIndex`] for details
+
+use alloc::collections::BTreeMap;
+
+use crate::{
+    generation::gw32,
+    generic_sparse::{self as sparse, GenericSparseArena},
+    key::ArenaIndex,
+    slotmap::ArenaKey,
+};
+
+/// An arena of unique values, which can look up values by key and keys by value
+///
+/// This uses the same arena as [`SlotMap`](crate::slotmap::SlotMap), along with a
+/// [`BTreeMap`] from each value to its key. So each value is stored twice, which is why
+/// `T: Clone` is required, and `T: Ord` is required to order the values in the map.
+///
+/// Every insertion and removal also updates the map, which costs an extra clone of the value
+/// and `O(log n)` comparisons. Values can't be mutated in place, since that would leave the map
+/// out of sync.
+///
+/// ```
+/// use ut_arena::by_value::ByValueIndex;
+/// use ut_arena::slotmap::ArenaKey;
+///
+/// let mut names = ByValueIndex::<String>::new();
+/// let alice: ArenaKey = names.insert("alice".to_string());
+/// let bob: ArenaKey = names.insert("bob".to_string());
+///
+/// assert_eq!(names.get_key_by_value("bob"), Some(bob));
+/// assert_eq!(names[alice], "alice");
+///
+/// // inserting an equal value gives back the existing key
+/// assert_eq!(names.insert("alice".to_string()), alice);
+///
+/// names.remove(alice);
+/// assert_eq!(names.get_key_by_value("alice"), None);
+/// assert_eq!(names.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct ByValueIndex<T, K = ArenaKey> {
+    arena: GenericSparseArena<T, (), gw32, u32>,
+    keys: BTreeMap<T, K>,
+}
+
+impl<T, K> ByValueIndex<T, K> {
+    /// Create a new empty [`ByValueIndex`]
+    pub const fn new() -> Self {
+        Self {
+            arena: GenericSparseArena::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+
+    /// The number of values in this [`ByValueIndex`]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if there are no values in this [`ByValueIndex`]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<T: Clone + Ord, K: ArenaIndex<(), gw32>> ByValueIndex<T, K> {
+    /// Insert a value, and return its key
+    ///
+    /// If an equal value is already in the [`ByValueIndex`], then `value` is dropped and the
+    /// key of the existing value is returned
+    pub fn insert(&mut self, value: T) -> K {
+        if let Some(&key) = self.keys.get(&value) {
+            return key;
+        }
+
+        let key = self.arena.insert(value.clone());
+        self.keys.insert(value, key);
+        key
+    }
+
+    /// Get a reference to the value associated with the key
+    ///
+    /// Returns None if the key is invalid
+    pub fn get(&self, key: K) -> Option<&T> {
+        self.arena.get(key)
+    }
+
+    /// Get the key of a value equal to `value`
+    pub fn get_key_by_value<Q>(&self, value: &Q) -> Option<K>
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.get(value).copied()
+    }
+
+    /// Returns true if there is a value equal to `value`
+    pub fn contains_value<Q>(&self, value: &Q) -> bool
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.contains_key(value)
+    }
+
+    /// Remove the value associated with the key
+    ///
+    /// Returns None if the key is invalid
+    pub fn try_remove(&mut self, key: K) -> Option<T> {
+        let value = self.arena.try_remove(key)?;
+        self.keys.remove(&value);
+        Some(value)
+    }
+
+    /// Remove the value associated with the key
+    ///
+    /// # Panics
+    ///
+    /// If the key is invalid
+    pub fn remove(&mut self, key: K) -> T {
+        let value = self.arena.remove(key);
+        self.keys.remove(&value);
+        value
+    }
+
+    /// Remove the value equal to `value`, and return its key and the stored value
+    pub fn remove_by_value<Q>(&mut self, value: &Q) -> Option<(K, T)>
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (_, key) = self.keys.remove_entry(value)?;
+        Some((key, self.arena.remove(key)))
+    }
+
+    /// Get an iterator over the keys and values, in the order of the keys
+    pub fn iter(&self) -> sparse::Iter<'_, K, T, (), gw32, u32> {
+        self.arena.iter()
+    }
+}
+
+impl<T, K> Default for ByValueIndex<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Ord, K: ArenaIndex<(), gw32>> core::ops::Index<K> for ByValueIndex<T, K> {
+    type Output = T;
+
+    fn index(&self, key: K) -> &Self::Output {
+        &self.arena[key]
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
 ///
 /// [`DenseSlab`] is instanciated as `GenericDenseArena<T, (), NoGeneration, usize>` and
 /// has an extra length field for compatiblity with the `slab` crate
+#[derive(Clone)]
 pub struct DenseSlab<T> {
     /// The underlying generic arena type
     pub arena: GenericDenseArena<T, (), NoGeneration, usize>,
@@ -41,16 +42,40 @@ impl<T> DenseSlab<T> {
         }
     }
 
+    /// Create a new [`DenseSlab`] with space for `capacity` elements
+    ///
+    /// see [`GenericDenseArena::reserve`]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            arena: GenericDenseArena::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for `additional` more elements
+    ///
+    /// see [`GenericDenseArena::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.arena.reserve(additional)
+    }
+
     /// Get the number of elements in the [`DenseSlab`]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.arena.tracker().len()
     }
 
     /// Returns true if there are no elements in the [`DenseSlab`]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.arena.tracker().is_empty()
     }
 
+    /// Get the insertion and removal counters of the [`DenseSlab`]
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.arena.tracker().stats()
+    }
+
     /// Insert a new value into a [`DenseSlab`]
     pub fn insert(&mut self, value: T) -> usize {
         self.arena.insert(value)
@@ -75,6 +100,11 @@ impl<T> DenseSlab<T> {
         self.arena.get(key)
     }
 
+    /// Returns true if the key is valid (in bounds, and the slot is filled)
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.arena.contains_key(key)
+    }
+
     /// Get a mutable reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or if the slot is empty)
@@ -122,6 +152,26 @@ impl<T> DenseSlab<T> {
         self.arena.remove(key)
     }
 
+    /// Try to remove the element associated with the key, and check if the
+    /// [`DenseSlab`] is empty after the removal
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    pub fn try_remove_and_is_empty(&mut self, key: usize) -> Option<(T, bool)> {
+        let value = self.try_remove(key)?;
+        Some((value, self.is_empty()))
+    }
+
+    /// Remove the element associated with the key, and check if the
+    /// [`DenseSlab`] is empty after the removal
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    pub fn remove_and_is_empty(&mut self, key: usize) -> (T, bool) {
+        let value = self.remove(key);
+        (value, self.is_empty())
+    }
+
     /// Remove the element associated with the key without checking
     /// if the key is invalid or out of bounds
     ///
@@ -164,6 +214,57 @@ impl<T> DenseSlab<T> {
             values,
         )
     }
+
+    /// An iterator over the keys and mutable references to the values in this [`DenseSlab`]
+    ///
+    /// The items are in the same order as [`DenseSlab::values`]
+    ///
+    /// ```
+    /// let mut slab = ut_arena::dense_slab::DenseSlab::new();
+    /// let a = slab.insert(1);
+    /// let b = slab.insert(2);
+    ///
+    /// for (key, value) in slab.iter_mut() {
+    ///     *value += key * 10;
+    /// }
+    ///
+    /// assert_eq!(slab[a], 1 + a * 10);
+    /// assert_eq!(slab[b], 2 + b * 10);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (keys, values) = self.keys_and_values_mut();
+        IterMut {
+            keys,
+            values: values.iter_mut(),
+        }
+    }
+}
+
+impl<T> Default for DenseSlab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects the values into a new [`DenseSlab`], and discards their keys
+impl<T> FromIterator<T> for DenseSlab<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T> Extend<T> for DenseSlab<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            self.insert(value);
+        });
+    }
 }
 
 impl<T> core::ops::Index<usize> for DenseSlab<T> {
@@ -211,3 +312,36 @@ impl DoubleEndedIterator for Keys<'_> {
         self.keys.nth_back(n)
     }
 }
+
+/// An iterator over the keys and mutable references to the values in a [`DenseSlab`]
+pub struct IterMut<'a, T> {
+    keys: Keys<'a>,
+    values: core::slice::IterMut<'a, T>,
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next()?, self.values.next()?))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        Some((self.keys.nth(n)?, self.values.nth(n)?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next_back()?, self.values.next_back()?))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        Some((self.keys.nth_back(n)?, self.values.nth_back(n)?))
+    }
+}
@@ -15,6 +15,7 @@ pub type ArenaKey = crate::key::ArenaKey<u32, gw32>;
 ///
 /// [`Slab`] is instanciated as `GenericSparseArena<T, (), NoGeneration, usize>` and
 /// has an extra length field for compatiblity with the `slab` crate
+#[derive(Clone)]
 pub struct DenseSlotMap<T> {
     /// the generic arena this [`DenseSlotMap`] is based on
     pub arena: GenericDenseArena<T, (), gw32, u32>,
@@ -40,22 +41,61 @@ impl<T> VacantSlot<'_, T> {
 
 impl<T> DenseSlotMap<T> {
     /// Create a new [`Slab`]
+    ///
+    /// ```
+    /// use ut_arena::dense_slotmap::DenseSlotMap;
+    ///
+    /// const EMPTY: DenseSlotMap<u8> = const { DenseSlotMap::new() };
+    /// const IS_EMPTY: bool = {
+    ///     let map = DenseSlotMap::<u8>::new();
+    ///     let is_empty = map.is_empty() && map.len() == 0;
+    ///     core::mem::forget(map);
+    ///     is_empty
+    /// };
+    ///
+    /// assert!(IS_EMPTY);
+    /// assert!(EMPTY.is_empty());
+    /// ```
     pub const fn new() -> Self {
         Self {
             arena: GenericDenseArena::new(),
         }
     }
 
+    /// Create a new [`DenseSlotMap`] with space for `capacity` elements
+    ///
+    /// see [`GenericDenseArena::reserve`]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            arena: GenericDenseArena::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for `additional` more elements
+    ///
+    /// see [`GenericDenseArena::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.arena.reserve(additional)
+    }
+
     /// Get the number of elements in the [`Slab`]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.arena.tracker().len()
     }
 
     /// Returns true if there are no elements in the [`Slab`]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.arena.tracker().is_empty()
     }
 
+    /// Get the insertion and removal counters of the [`DenseSlotMap`]
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.arena.tracker().stats()
+    }
+
     /// Insert a new value into a [`Slab`]
     pub fn insert(&mut self, value: T) -> usize {
         self.arena.insert(value)
@@ -80,6 +120,11 @@ impl<T> DenseSlotMap<T> {
         self.arena.get(key)
     }
 
+    /// Returns true if the key is valid (in bounds, and the slot is filled)
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.arena.contains_key(key)
+    }
+
     /// Get a mutable reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or if the slot is empty)
@@ -127,6 +172,26 @@ impl<T> DenseSlotMap<T> {
         self.arena.remove(key)
     }
 
+    /// Try to remove the element associated with the key, and check if the
+    /// [`DenseSlotMap`] is empty after the removal
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    pub fn try_remove_and_is_empty(&mut self, key: usize) -> Option<(T, bool)> {
+        let value = self.try_remove(key)?;
+        Some((value, self.is_empty()))
+    }
+
+    /// Remove the element associated with the key, and check if the
+    /// [`DenseSlotMap`] is empty after the removal
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    pub fn remove_and_is_empty(&mut self, key: usize) -> (T, bool) {
+        let value = self.remove(key);
+        (value, self.is_empty())
+    }
+
     /// Remove the element associated with the key without checking
     /// if the key is invalid or out of bounds
     ///
@@ -169,6 +234,44 @@ impl<T> DenseSlotMap<T> {
             values,
         )
     }
+
+    /// An iterator over the keys and mutable references to the values in this [`DenseSlotMap`]
+    ///
+    /// The items are in the same order as [`DenseSlotMap::values`]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (keys, values) = self.keys_and_values_mut();
+        IterMut {
+            keys,
+            values: values.iter_mut(),
+        }
+    }
+}
+
+impl<T> Default for DenseSlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects the values into a new [`DenseSlotMap`], and discards their keys
+impl<T> FromIterator<T> for DenseSlotMap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T> Extend<T> for DenseSlotMap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            self.insert(value);
+        });
+    }
 }
 
 impl<T> core::ops::Index<ArenaKey> for DenseSlotMap<T> {
@@ -216,3 +319,36 @@ impl DoubleEndedIterator for Keys<'_> {
         self.keys.nth_back(n)
     }
 }
+
+/// An iterator over the keys and mutable references to the values in a [`DenseSlotMap`]
+pub struct IterMut<'a, T> {
+    keys: Keys<'a>,
+    values: core::slice::IterMut<'a, T>,
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (ArenaKey, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next()?, self.values.next()?))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        Some((self.keys.nth(n)?, self.values.nth(n)?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next_back()?, self.values.next_back()?))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        Some((self.keys.nth_back(n)?, self.values.nth_back(n)?))
+    }
+}
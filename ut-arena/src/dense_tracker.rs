@@ -7,7 +7,7 @@
 //!
 //! * Each time you call [`VacantSlot::insert`], you must push an element into the array(s)
 //! * Each time you call [`GenericDenseTracker::remove`] (or it's variants), successfully
-//!  you must [`Vec::swap_remove`] the corresponding element out of the array(s)
+//!   you must [`Vec::swap_remove`] the corresponding element out of the array(s)
 //!
 //! If you do these two things, then all indices in the
 //! [`GenericDenseTracker`](crate::generic_dense::GenericDenseArena) are guaranteed
@@ -60,6 +60,69 @@ impl<G: Generation, I: InternalIndex> GenericDenseTracker<(), G, I> {
             index: GenericSparseArena::new(),
         }
     }
+
+    /// Create a new [`GenericDenseTracker`] with space for `capacity` keys
+    ///
+    /// see [`GenericDenseTracker::reserve`]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut tracker = Self::new();
+        tracker.reserve(capacity);
+        tracker
+    }
+}
+
+/// Clones the tracker, so every key which is valid in this tracker is also valid in the clone
+impl<G: Generation, I: InternalIndex> Clone for GenericDenseTracker<(), G, I> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+/// Serializes the underlying sparse arena, which maps keys to positions
+///
+/// The positions are restored from the sparse arena when deserializing, so every
+/// key which is valid in this tracker is also valid in the deserialized tracker.
+#[cfg(feature = "serde")]
+impl<G: Generation + serde::Serialize, I: InternalIndex + serde::Serialize> serde::Serialize
+    for GenericDenseTracker<(), G, I>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.index, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, G: Generation + serde::Deserialize<'de>, I: InternalIndex + serde::Deserialize<'de>>
+    serde::Deserialize<'de> for GenericDenseTracker<(), G, I>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let index = GenericSparseArena::<I, (), G, I>::deserialize(deserializer)?;
+        let mut keys = alloc::vec![None; index.values().count()];
+
+        for (slot, &position) in index.iter::<usize>() {
+            let key = keys
+                .get_mut(position.to_usize())
+                .ok_or_else(|| D::Error::custom("found a position past the end of the tracker"))?;
+
+            if key.replace(I::from_usize(slot)).is_some() {
+                return Err(D::Error::custom("found two keys with the same position"));
+            }
+        }
+
+        // there are as many positions as keys, and no duplicate or out of bounds
+        // positions, so every position has a key
+        let keys = keys
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| D::Error::custom("found a position without a key"))?;
+
+        Ok(Self { keys, index })
+    }
 }
 
 impl<G: Generation, I: InternalIndex> Default for GenericDenseTracker<(), G, I> {
@@ -85,6 +148,16 @@ impl<O, G: Generation, I: InternalIndex> GenericDenseTracker<O, G, I> {
     pub fn owner(&self) -> &O {
         self.index.owner()
     }
+
+    /// Get a mutable reference to the owner of this type's keys
+    ///
+    /// # Safety
+    ///
+    /// see [`UtVec::owner_mut`](ut_vec::UtVec::owner_mut)
+    pub unsafe fn owner_mut(&mut self) -> &mut O {
+        // SAFETY: ensured by caller
+        unsafe { self.index.owner_mut() }
+    }
 }
 
 impl<O: ?Sized, G: Generation, I: InternalIndex> VacantSlot<'_, O, G, I> {
@@ -110,6 +183,14 @@ impl<O: ?Sized, G: Generation, I: InternalIndex> VacantSlot<'_, O, G, I> {
 }
 
 impl<O: ?Sized, G: Generation, I: InternalIndex> GenericDenseTracker<O, G, I> {
+    /// Reserve space for `additional` more keys
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.keys.reserve(additional);
+        self.index.reserve(additional);
+    }
+
     /// Access a vacant slot in the arena
     pub fn vacant_slot(&mut self, len: usize) -> VacantSlot<'_, O, G, I> {
         assert_eq!(self.keys.len(), len);
@@ -119,18 +200,55 @@ impl<O: ?Sized, G: Generation, I: InternalIndex> GenericDenseTracker<O, G, I> {
         }
     }
 
+    /// Returns true if a slot can be reserved without running out of indices in `I`
+    #[inline]
+    pub(crate) fn can_insert(&self) -> bool {
+        self.index.can_insert()
+    }
+
+    /// Check if generations wrap around instead of leaking slots when removing values
+    ///
+    /// see [`GenericSparseArena::wraps_generations`]
+    #[inline]
+    pub fn wraps_generations(&self) -> bool {
+        self.index.wraps_generations()
+    }
+
+    /// Set if generations should wrap around instead of leaking slots when removing values
+    ///
+    /// see [`GenericSparseArena::set_wrap_generations`]
+    #[inline]
+    pub fn set_wrap_generations(&mut self, wrap: bool) {
+        self.index.set_wrap_generations(wrap)
+    }
+
+    /// Get the insertion and removal counters of this tracker
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.index.stats()
+    }
+
     /// The number of elements in the arena
     #[inline]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.keys.len()
     }
 
     /// Returns true if there are no elements in the arena
     #[inline]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.keys.is_empty()
     }
 
+    /// Returns true if the key is valid (in bounds, and has the correct generation)
+    #[inline]
+    pub fn contains_key<K: ArenaIndex<O, G>>(&self, key: K) -> bool {
+        self.index.contains_key(key)
+    }
+
     /// Get the index into the array associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or incorrect generation)
@@ -201,6 +319,16 @@ impl<O: ?Sized, G: Generation, I: InternalIndex> GenericDenseTracker<O, G, I> {
         unsafe { self.index.key_of_unchecked(index) }
     }
 
+    /// Get the key associated with a position in the associated array
+    ///
+    /// Returns [`None`] if the position is out of bounds
+    #[inline]
+    pub fn key_at_position<K: ArenaIndex<O, G>>(&self, position: usize) -> Option<K> {
+        let index = self.keys.get(position)?;
+        // SAFETY: all keys in self.keys are valid and in bounds
+        Some(unsafe { self.index.key_of_unchecked(index.to_usize()) })
+    }
+
     fn remove_at(&mut self, index_fwd: I) -> usize {
         if self.keys.is_empty() {
             debug_assert!(false);
@@ -260,6 +388,49 @@ impl<O: ?Sized, G: Generation, I: InternalIndex> GenericDenseTracker<O, G, I> {
         self.remove_at(index_fwd)
     }
 
+    fn shift_remove_at(&mut self, index_fwd: I) -> usize {
+        let index = index_fwd.to_usize();
+        self.keys.remove(index);
+
+        // every key after the removed one has been shifted down by one
+        // so we need to update the forward mapping for all of them
+        for (i, &key) in self.keys[index..].iter().enumerate() {
+            // SAFETY: self.keys only contains valid keys into self.index
+            unsafe { *self.index.get_unchecked_mut(key.to_usize()) = I::from_usize(index + i) }
+        }
+
+        index
+    }
+
+    /// Try to remove the element associated with the key, while preserving the
+    /// order of all other elements
+    ///
+    /// Each time this succeeds, you must [`Vec::remove`] the corresponding element
+    /// out of the array(s), instead of [`Vec::swap_remove`]
+    ///
+    /// This is O(n) in the number of elements after the removed element
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    #[inline]
+    pub fn try_shift_remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> Option<usize> {
+        let index_fwd = self.index.try_remove(key)?;
+        Some(self.shift_remove_at(index_fwd))
+    }
+
+    /// Remove the element associated with the key, while preserving the
+    /// order of all other elements
+    ///
+    /// see [`GenericDenseTracker::try_shift_remove`] for details
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    #[inline]
+    pub fn shift_remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> usize {
+        let index_fwd = self.index.remove(key);
+        self.shift_remove_at(index_fwd)
+    }
+
     /// Get an iterator over all the keys in the arena
     ///
     /// This iterator will yield exactly `self.len` elements
@@ -19,10 +19,12 @@ use core::{fmt, hash::Hash};
 /// * when creating a key for a filled slot, you should call [`Generation::to_filled`]
 ///     * this creates a more optimizated representation of the generation for keys
 ///       for example, for `NoGeneration` this is just a `()`
+/// * you can convert a key's generation back to the slot's generation via
+///   [`Generation::from_filled`]
 /// * you can check if a key's generation matches a slot's generation via [`Generation::matches`]
 ///     * and [`Generation::write_mismatch`] writes the error message in case of these don't match
 /// * is_empty, and is_filled can be used to check if the slot for this generation is filled or
-///       empty
+///   empty
 ///
 /// # Safety
 ///
@@ -44,6 +46,8 @@ use core::{fmt, hash::Hash};
 ///     if g.matches(filled) {
 ///         assert!(g.is_filled())
 ///     }
+///
+///     assert!(G::from_filled(filled).matches(filled));
 /// }
 /// ```
 pub unsafe trait Generation: Copy + Ord + Hash + core::fmt::Debug {
@@ -73,6 +77,22 @@ pub unsafe trait Generation: Copy + Ord + Hash + core::fmt::Debug {
     /// The generation must currently be filled
     unsafe fn try_empty(self) -> Result<Self, Self::TryEmptyError>;
 
+    /// Get the next generation, using the arena's wrapping flag
+    ///
+    /// The flag is stored once per arena, so generations like [`HybridGeneration`] can
+    /// choose between wrapping and saturating at runtime. By default this ignores the flag
+    /// and defers to [`Generation::try_empty`]
+    ///
+    /// # Safety
+    ///
+    /// The generation must currently be filled
+    #[inline]
+    unsafe fn try_empty_with(self, wrap: bool) -> Result<Self, Self::TryEmptyError> {
+        let _ = wrap;
+        // SAFETY: ensured by caller
+        unsafe { self.try_empty() }
+    }
+
     /// Convert a filled generation to [`Generation::Filled`]
     ///
     /// # Safety
@@ -80,6 +100,11 @@ pub unsafe trait Generation: Copy + Ord + Hash + core::fmt::Debug {
     /// The generation must be filled
     unsafe fn to_filled(self) -> Self::Filled;
 
+    /// Convert a filled generation back to the generation of the slot it was created from
+    ///
+    /// This must return a filled generation which matches `filled`
+    fn from_filled(filled: Self::Filled) -> Self;
+
     /// Check if a generation matches the filled generation
     fn matches(self, filled: Self::Filled) -> bool;
 
@@ -91,6 +116,15 @@ pub unsafe trait Generation: Copy + Ord + Hash + core::fmt::Debug {
         f: &mut fmt::Formatter<'_>,
     ) -> fmt::Result;
 
+    /// Writes a filled generation for the [`Display`](fmt::Display) impl of
+    /// [`ArenaKey`](crate::key::ArenaKey)
+    ///
+    /// By default this uses the [`Debug`](fmt::Debug) impl of [`Generation::Filled`]
+    #[inline]
+    fn write_filled(filled: Self::Filled, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&filled, f)
+    }
+
     /// Check if the generation is in the empty variant
     fn is_empty(self) -> bool;
 
@@ -99,20 +133,73 @@ pub unsafe trait Generation: Copy + Ord + Hash + core::fmt::Debug {
     fn is_filled(self) -> bool {
         !self.is_empty()
     }
+
+    /// The number of times a slot with this generation can be emptied (after it is filled)
+    /// before the generation is exhausted, with the given wrapping flag
+    ///
+    /// Returns [`None`] if the generation is never exhausted, which is the default
+    #[inline]
+    fn remaining_cycles(self, wrap: bool) -> Option<u128> {
+        let _ = wrap;
+        None
+    }
 }
 
+/// A marker for [`Generation`]s which never hand out the same filled generation twice
+/// for a slot
+///
+/// Instead of wrapping around, these generations are exhausted, and the slot is leaked. So
+/// arenas using these generations never give out duplicate keys, which rules out the ABA-problem.
+///
+/// This is implemented for [`DefaultGeneration`] and the saturating `gN` types, but not the
+/// wrapping `gwN` types, [`HybridGeneration`] or [`NoGeneration`].
+///
+/// ```
+/// use ut_arena::generation::{g16, UniqueGeneration};
+/// use ut_arena::generic_sparse::GenericSparseArena;
+///
+/// fn new_entity_store<T, G: UniqueGeneration>() -> GenericSparseArena<T, (), G> {
+///     GenericSparseArena::new()
+/// }
+///
+/// let store = new_entity_store::<char, g16>();
+/// ```
+///
+/// ```compile_fail
+/// # use ut_arena::generation::{gw16, UniqueGeneration};
+/// # use ut_arena::generic_sparse::GenericSparseArena;
+/// # fn new_entity_store<T, G: UniqueGeneration>() -> GenericSparseArena<T, (), G> {
+/// #     GenericSparseArena::new()
+/// # }
+/// let store = new_entity_store::<char, gw16>();
+/// ```
+pub trait UniqueGeneration: Generation + crate::seal::Seal {}
+
+impl crate::seal::Seal for DefaultGeneration {}
+impl UniqueGeneration for DefaultGeneration {}
+
 type DefaultGenerationInner = g32;
 
 /// The default generation type, currently just a thin wrapper around [`g32`]
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(kani, derive(kani::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct DefaultGeneration(DefaultGenerationInner);
 
 /// The default generation's filled type, currently just a thin wrapper around [`FilledGsize`]'
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(kani, derive(kani::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct DefaultGenerationFilled(<DefaultGenerationInner as Generation>::Filled);
 
 #[cfg(kani)]
@@ -147,6 +234,11 @@ unsafe impl Generation for DefaultGeneration {
         DefaultGenerationFilled(unsafe { self.0.to_filled() })
     }
 
+    #[inline]
+    fn from_filled(filled: Self::Filled) -> Self {
+        Self(DefaultGenerationInner::from_filled(filled.0))
+    }
+
     #[inline]
     fn matches(self, filled: Self::Filled) -> bool {
         self.0.matches(filled.0)
@@ -162,6 +254,11 @@ unsafe impl Generation for DefaultGeneration {
         self.0.write_mismatch(filled.0, index, f)
     }
 
+    #[inline]
+    fn write_filled(filled: Self::Filled, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DefaultGenerationInner::write_filled(filled.0, f)
+    }
+
     #[inline]
     fn is_empty(self) -> bool {
         self.0.is_empty()
@@ -171,6 +268,11 @@ unsafe impl Generation for DefaultGeneration {
     fn is_filled(self) -> bool {
         self.0.is_filled()
     }
+
+    #[inline]
+    fn remaining_cycles(self, wrap: bool) -> Option<u128> {
+        self.0.remaining_cycles(wrap)
+    }
 }
 
 /// The generation type to ignore ABA issues
@@ -179,6 +281,11 @@ unsafe impl Generation for DefaultGeneration {
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(kani, derive(kani::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct NoGeneration(bool);
 
 #[cfg(kani)]
@@ -209,6 +316,11 @@ unsafe impl Generation for NoGeneration {
     #[inline]
     unsafe fn to_filled(self) -> Self::Filled {}
 
+    #[inline]
+    fn from_filled((): Self::Filled) -> Self {
+        Self(true)
+    }
+
     #[inline]
     fn matches(self, (): Self::Filled) -> bool {
         self.0
@@ -232,6 +344,294 @@ unsafe impl Generation for NoGeneration {
     }
 }
 
+/// A wrapper around another generation which checks for illegal transitions in debug builds
+///
+/// Filling a filled generation, or emptying or converting an empty generation, panics
+/// when `debug_assertions` are enabled. The arenas never do this, so this is useful for
+/// catching bugs in code which manages slots directly. In release builds, this behaves exactly
+/// like `G`.
+///
+/// ```should_panic
+/// use ut_arena::generation::{DebugGeneration, Generation};
+///
+/// let g = DebugGeneration::<ut_arena::generation::g8>::EMPTY;
+/// // SAFETY: g is empty
+/// let g = unsafe { g.fill() };
+/// // this panics since g is already filled
+/// let g = unsafe { g.fill() };
+/// # #[cfg(not(debug_assertions))]
+/// # panic!();
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct DebugGeneration<G = DefaultGeneration>(G);
+
+impl<G: UniqueGeneration> crate::seal::Seal for DebugGeneration<G> {}
+impl<G: UniqueGeneration> UniqueGeneration for DebugGeneration<G> {}
+
+// SAFETY: defers to `G`, only adding extra checks which panic
+unsafe impl<G: Generation> Generation for DebugGeneration<G> {
+    const EMPTY: Self = Self(G::EMPTY);
+
+    type TryEmptyError = G::TryEmptyError;
+    type Filled = G::Filled;
+
+    #[inline]
+    unsafe fn fill(self) -> Self {
+        debug_assert!(
+            self.is_empty(),
+            "tried to fill a filled generation: {self:?}"
+        );
+        // SAFETY: ensured by caller
+        Self(unsafe { self.0.fill() })
+    }
+
+    #[inline]
+    unsafe fn try_empty(self) -> Result<Self, Self::TryEmptyError> {
+        debug_assert!(
+            self.is_filled(),
+            "tried to empty an empty generation: {self:?}"
+        );
+        // SAFETY: ensured by caller
+        unsafe { self.0.try_empty() }.map(Self)
+    }
+
+    #[inline]
+    unsafe fn try_empty_with(self, wrap: bool) -> Result<Self, Self::TryEmptyError> {
+        debug_assert!(
+            self.is_filled(),
+            "tried to empty an empty generation: {self:?}"
+        );
+        // SAFETY: ensured by caller
+        unsafe { self.0.try_empty_with(wrap) }.map(Self)
+    }
+
+    #[inline]
+    unsafe fn to_filled(self) -> Self::Filled {
+        debug_assert!(
+            self.is_filled(),
+            "tried to convert an empty generation: {self:?}"
+        );
+        // SAFETY: ensured by caller
+        unsafe { self.0.to_filled() }
+    }
+
+    #[inline]
+    fn from_filled(filled: Self::Filled) -> Self {
+        Self(G::from_filled(filled))
+    }
+
+    #[inline]
+    fn matches(self, filled: Self::Filled) -> bool {
+        self.0.matches(filled)
+    }
+
+    #[inline]
+    fn write_mismatch(
+        self,
+        filled: Self::Filled,
+        index: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        self.0.write_mismatch(filled, index, f)
+    }
+
+    #[inline]
+    fn write_filled(filled: Self::Filled, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        G::write_filled(filled, f)
+    }
+
+    #[inline]
+    fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    fn is_filled(self) -> bool {
+        self.0.is_filled()
+    }
+
+    #[inline]
+    fn remaining_cycles(self, wrap: bool) -> Option<u128> {
+        self.0.remaining_cycles(wrap)
+    }
+}
+
+/// Implements serde for a filled generation as it's underlying integer
+///
+/// Filled generations are always odd, so deserializing an even integer fails. Otherwise
+/// a key could match an empty slot with the same generation.
+macro_rules! serde_filled {
+    ($name:ident $inner:ident) => {
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serde::Serialize::serialize(&self.0.get(), serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let generation = <$inner as serde::Deserialize>::deserialize(deserializer)?;
+
+                if generation & 1 == 1 {
+                    Ok(Self(
+                        generation.try_into().map_err(serde::de::Error::custom)?,
+                    ))
+                } else {
+                    Err(serde::de::Error::custom(
+                        "expected a filled generation, which must be odd",
+                    ))
+                }
+            }
+        }
+    };
+}
+
+/// A 32-bit generation which either saturates or wraps, depending on the arena's flag
+///
+/// The flag is a [`bool`] which is stored once per arena, instead of once per slot.
+/// When it is `false` (the default), this behaves like [`g32`] and exhausted slots are leaked.
+/// When it is `true`, this behaves like [`gw32`] and generations wrap around.
+///
+/// The flag can be changed at any time with
+/// [`GenericSparseArena::set_wrap_generations`](crate::generic_sparse::GenericSparseArena::set_wrap_generations),
+/// and only affects removals after the change.
+///
+/// * switching to wrapping means that new keys may compare equal to keys which were handed
+///   out before the generation wrapped around, so old keys may access new values.
+/// * slots which were exhausted while saturating stay exhausted, even after switching to wrapping
+///
+/// ```
+/// use ut_arena::generation::HybridGeneration;
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+///
+/// let mut arena = Arena::<char, (), HybridGeneration>::new();
+/// arena.set_wrap_generations(true);
+/// assert!(arena.wraps_generations());
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct HybridGeneration(u32);
+
+/// The filled representation of [`HybridGeneration`]
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FilledHybridGeneration(core::num::NonZeroU32);
+
+serde_filled!(FilledHybridGeneration u32);
+
+#[cfg(kani)]
+impl kani::Arbitrary for FilledHybridGeneration {
+    fn any() -> Self {
+        let inner = kani::any::<core::num::NonZeroU32>();
+        // all filled generations must be odd, so we can let
+        // kani assume that.
+        kani::assume(inner.get() & 1 == 1);
+        Self(inner)
+    }
+}
+
+#[cfg(kani)]
+#[kani::proof]
+fn proof_hybrid_generation() {
+    let g = kani::any::<HybridGeneration>();
+    let f = kani::any::<FilledHybridGeneration>();
+    test_generation(g, f);
+}
+
+// SAFETY: this uses the same representation as the `gN` and `gwN` types
+unsafe impl Generation for HybridGeneration {
+    const EMPTY: Self = Self(0);
+
+    type TryEmptyError = ();
+    type Filled = FilledHybridGeneration;
+
+    #[inline]
+    unsafe fn fill(self) -> Self {
+        debug_assert!(self.is_empty());
+        Self(self.0 | 1)
+    }
+
+    #[inline]
+    unsafe fn try_empty(self) -> Result<Self, Self::TryEmptyError> {
+        debug_assert!(self.is_filled());
+        self.0.checked_add(1).map(Self).ok_or(())
+    }
+
+    #[inline]
+    unsafe fn try_empty_with(self, wrap: bool) -> Result<Self, Self::TryEmptyError> {
+        debug_assert!(self.is_filled());
+        if wrap {
+            Ok(Self(self.0.wrapping_add(1)))
+        } else {
+            // SAFETY: ensured by caller
+            unsafe { self.try_empty() }
+        }
+    }
+
+    #[inline]
+    unsafe fn to_filled(self) -> Self::Filled {
+        debug_assert!(self.is_filled());
+        // SAFETY: all filled generations have the least significant bit set, so mut be
+        // non-zero
+        FilledHybridGeneration(unsafe { core::num::NonZeroU32::new_unchecked(self.0) })
+    }
+
+    #[inline]
+    fn from_filled(filled: Self::Filled) -> Self {
+        Self(filled.0.get())
+    }
+
+    #[inline]
+    fn matches(self, filled: Self::Filled) -> bool {
+        self.0 == filled.0.get()
+    }
+
+    fn write_mismatch(
+        self,
+        filled: Self::Filled,
+        index: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "tried to access arena with an expired key at index {index} with generation: {filled:?}, but expected generation: {self:?}"
+        )
+    }
+
+    #[inline]
+    fn write_filled(filled: Self::Filled, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&filled.0, f)
+    }
+
+    #[inline]
+    fn is_empty(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    #[inline]
+    fn remaining_cycles(self, wrap: bool) -> Option<u128> {
+        if wrap {
+            None
+        } else {
+            Some(((u32::MAX - (self.0 | 1)) / 2) as u128)
+        }
+    }
+}
+
 macro_rules! prim_impl {
     (ty wrapping) => {
         core::convert::Infallible
@@ -245,6 +645,13 @@ macro_rules! prim_impl {
     (fn saturating($self:ident, $Self:ident)) => {
         $self.0.checked_add(1).map($Self).ok_or(())
     };
+    (remaining wrapping($self:ident, $inner:ident)) => {
+        None
+    };
+    (remaining saturating($self:ident, $inner:ident)) => {
+        // filled generations are odd, and each cycle adds 2 until reaching MAX (which is odd)
+        Some((($inner::MAX - ($self.0 | 1)) / 2) as u128)
+    };
 }
 
 macro_rules! prim {
@@ -265,6 +672,7 @@ macro_rules! prim {
         #[allow(non_camel_case_types)]
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         #[cfg_attr(kani, derive(kani::Arbitrary))]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
         pub struct $name($inner);
         $(#[$meta_filled])*
         #[repr(transparent)]
@@ -272,6 +680,8 @@ macro_rules! prim {
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub struct $name_filled(core::num::$filled_inner);
 
+        serde_filled!($name_filled $inner);
+
         const _: () = {
             #[cfg(kani)]
             #[kani::proof]
@@ -339,6 +749,11 @@ macro_rules! prim {
                 prim_impl!(fn $kind(self, Self))
             }
 
+            #[inline]
+            fn from_filled(filled: Self::Filled) -> Self {
+                Self(filled.0.get())
+            }
+
             #[inline]
             fn matches(self, filled: Self::Filled) -> bool {
                 self.0 == filled.0.get()
@@ -369,6 +784,11 @@ macro_rules! prim {
                 // we represent empty as any even numbered generation
                 self.0 & 1 == 0
             }
+
+            #[inline]
+            fn remaining_cycles(self, _wrap: bool) -> Option<u128> {
+                prim_impl!(remaining $kind(self, $inner))
+            }
         }
     };
 }
@@ -395,6 +815,9 @@ macro_rules! prim_saturating {
 
             saturating
         }
+
+        impl crate::seal::Seal for $name {}
+        impl UniqueGeneration for $name {}
     };
 }
 
@@ -542,3 +965,17 @@ where
         assert!(unsafe { g.to_filled() } == filled);
     }
 }
+
+#[cfg(feature = "slotmap-compat")]
+impl FilledGw32 {
+    /// Create a filled generation from a `slotmap` version, which are also always odd
+    pub(crate) fn from_slotmap_version(version: u32) -> Self {
+        // SAFETY: version | 1 is odd, so it can't be zero
+        Self(unsafe { core::num::NonZeroU32::new_unchecked(version | 1) })
+    }
+
+    /// Convert this filled generation to a `slotmap` version
+    pub(crate) fn slotmap_version(self) -> u32 {
+        self.0.get()
+    }
+}
@@ -2,15 +2,16 @@
 //!
 //! see [`GenericDenseArena`] for details
 
-use core::ops;
+use core::{marker::PhantomData, ops};
 
 use alloc::vec::Vec;
 
 use crate::{
     dense_tracker::{self, GenericDenseTracker},
     generation::{DefaultGeneration, Generation},
+    generic_sparse::Deferred,
     internal_index::InternalIndex,
-    key::ArenaIndex,
+    key::{ArenaIndex, SwapError},
 };
 
 /// [`GenericDenseArena`] is the canonical implementation of how to use [`GenericDenseTracker`]
@@ -38,6 +39,59 @@ pub struct VacantSlot<
     vec: &'a mut Vec<T>,
 }
 
+/// A batch of vacant slots into a [`GenericDenseArena`], created via
+/// [`GenericDenseArena::vacant_slots`]
+///
+/// This isn't an [`Iterator`], since each [`VacantSlot`] borrows the arena, so it
+/// must be filled (or dropped) before the next one can be accessed.
+pub struct VacantSlots<
+    'a,
+    T,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    arena: &'a mut GenericDenseArena<T, O, G, I>,
+    remaining: usize,
+}
+
+/// An iterator over keys and clones of values in a [`GenericDenseArena`], created from
+/// [`GenericDenseArena::cloned_entries`]
+pub struct ClonedEntries<
+    'a,
+    K,
+    T,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    keys: dense_tracker::Keys<'a, K, O, G, I>,
+    values: core::slice::Iter<'a, T>,
+}
+
+/// An iterator which removes elements from a [`GenericDenseArena`], created from
+/// [`GenericDenseArena::extract_if`]
+pub struct ExtractIf<
+    'a,
+    K,
+    T,
+    F,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    arena: &'a mut GenericDenseArena<T, O, G, I>,
+    index: usize,
+    f: F,
+    _key: PhantomData<fn() -> K>,
+}
+
+/// An iterator over keys and values in a [`GenericDenseArena`], sorted by key, created from
+/// [`GenericDenseArena::iter_sorted_by_key`]
+pub struct SortedByKey<'a, K, T> {
+    entries: alloc::vec::IntoIter<(K, &'a T)>,
+}
+
 impl<T, G: Generation, I: InternalIndex> GenericDenseArena<T, (), G, I> {
     /// Create a new [`GenericDenseArena`]
     pub const fn new() -> Self {
@@ -46,6 +100,129 @@ impl<T, G: Generation, I: InternalIndex> GenericDenseArena<T, (), G, I> {
             tracker: GenericDenseTracker::new(),
         }
     }
+
+    /// Create a new [`GenericDenseArena`] with space for `capacity` values
+    ///
+    /// see [`GenericDenseArena::reserve`]
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::with_capacity(16);
+    /// let values = arena.values().as_ptr();
+    ///
+    /// for i in 0..16 {
+    ///     let _: usize = arena.insert(i);
+    /// }
+    ///
+    /// assert_eq!(arena.values().as_ptr(), values);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            tracker: GenericDenseTracker::with_capacity(capacity),
+        }
+    }
+}
+
+/// Clones the values and the tracker, so every key which is valid in this arena is
+/// also valid in the clone
+///
+/// This is only implemented for the `()` owner, since cloning a unique owner would
+/// allow keys from one arena to be used in another.
+///
+/// ```
+/// use ut_arena::generic_dense::GenericDenseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<String>::new();
+/// let a: ArenaKey = arena.insert("a".to_string());
+/// let b: ArenaKey = arena.insert("b".to_string());
+/// arena.remove(a);
+///
+/// let mut clone = arena.clone();
+/// assert_eq!(clone[b], "b");
+/// assert!(!clone.contains_key(a));
+///
+/// clone[b].push('!');
+/// assert_eq!(arena[b], "b");
+/// assert_eq!(clone[b], "b!");
+/// ```
+impl<T: Clone, G: Generation, I: InternalIndex> Clone for GenericDenseArena<T, (), G, I> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            tracker: self.tracker.clone(),
+        }
+    }
+}
+
+/// The serialized representation of a [`GenericDenseArena`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "GenericDenseArena")]
+struct ArenaRepr<T, Tracker> {
+    values: Vec<T>,
+    tracker: Tracker,
+}
+
+/// Serializes the values, along with the tracker which maps keys to values
+///
+/// So every key which is valid in this arena is also valid in the deserialized arena, and keys
+/// which were removed stay invalid. This is only implemented for the `()` owner, since
+/// deserializing a unique owner would allow keys from one arena to be used in another.
+///
+/// ```
+/// use ut_arena::generic_dense::GenericDenseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey = arena.insert('a');
+/// let b: ArenaKey = arena.insert('b');
+/// arena.remove(a);
+///
+/// let json = serde_json::to_string(&arena).unwrap();
+/// let arena: Arena<char> = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(arena.get(a), None);
+/// assert_eq!(arena[b], 'b');
+/// ```
+#[cfg(feature = "serde")]
+impl<T, G, I> serde::Serialize for GenericDenseArena<T, (), G, I>
+where
+    T: serde::Serialize,
+    G: Generation + serde::Serialize,
+    I: InternalIndex + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut arena = serializer.serialize_struct("GenericDenseArena", 2)?;
+        arena.serialize_field("values", &self.values)?;
+        arena.serialize_field("tracker", &self.tracker)?;
+        arena.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, G, I> serde::Deserialize<'de> for GenericDenseArena<T, (), G, I>
+where
+    T: serde::Deserialize<'de>,
+    G: Generation + serde::Deserialize<'de>,
+    I: InternalIndex + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ArenaRepr { values, tracker } =
+            ArenaRepr::<T, GenericDenseTracker<(), G, I>>::deserialize(deserializer)?;
+
+        if values.len() != tracker.len() {
+            return Err(serde::de::Error::custom(
+                "the number of values doesn't match the number of keys",
+            ));
+        }
+
+        Ok(Self { values, tracker })
+    }
 }
 
 impl<T, G: Generation, I: InternalIndex> Default for GenericDenseArena<T, (), G, I> {
@@ -54,6 +231,35 @@ impl<T, G: Generation, I: InternalIndex> Default for GenericDenseArena<T, (), G,
     }
 }
 
+/// Collects the values into a new arena, and discards their keys
+///
+/// ```
+/// use ut_arena::generic_dense::GenericDenseArena as Arena;
+///
+/// let mut arena: Arena<char> = "hello".chars().collect();
+/// arena.extend(['!']);
+/// assert_eq!(arena.values().iter().collect::<String>(), "hello!");
+/// ```
+impl<T, G: Generation, I: InternalIndex> FromIterator<T> for GenericDenseArena<T, (), G, I> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T, G: Generation, I: InternalIndex> Extend<T> for GenericDenseArena<T, (), G, I> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        self.values.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            let _: usize = self.insert(value);
+        });
+    }
+}
+
 #[cfg(feature = "unique-types")]
 impl<T, O, G: Generation, I: InternalIndex> GenericDenseArena<T, O, G, I> {
     /// Create a new [`GenericDenseArena`] with the given owner
@@ -71,6 +277,16 @@ impl<T, O, G: Generation, I: InternalIndex> GenericDenseArena<T, O, G, I> {
     pub fn owner(&self) -> &O {
         self.tracker.owner()
     }
+
+    /// Get a mutable reference to the owner of this type's keys
+    ///
+    /// # Safety
+    ///
+    /// see [`UtVec::owner_mut`](ut_vec::UtVec::owner_mut)
+    pub unsafe fn owner_mut(&mut self) -> &mut O {
+        // SAFETY: ensured by caller
+        unsafe { self.tracker.owner_mut() }
+    }
 }
 
 impl<T, O: ?Sized, G: Generation, I: InternalIndex> VacantSlot<'_, T, O, G, I> {
@@ -79,6 +295,11 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> VacantSlot<'_, T, O, G, I> {
         self.slot.key()
     }
 
+    /// Get the position that the value will be placed at in [`GenericDenseArena::values`]
+    pub fn position(&self) -> usize {
+        self.slot.position()
+    }
+
     /// Insert an element into this slot
     pub fn insert(self, value: T) {
         let index = self.slot.position();
@@ -110,11 +331,51 @@ where
         }
     }
 
+    /// Access `n` vacant slots in the arena, reserving space for all of them up front
+    ///
+    /// Slots which are dropped without being filled don't change the arena.
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<usize>::new();
+    /// let mut slots = arena.vacant_slots(3);
+    /// let mut keys = Vec::new();
+    ///
+    /// while let Some(slot) = slots.next_slot() {
+    ///     keys.push(slot.key::<ArenaKey>());
+    ///     let position = slot.position();
+    ///     slot.insert(position * 10);
+    /// }
+    ///
+    /// assert_eq!(arena.values(), [0, 10, 20]);
+    /// assert_eq!(arena[keys[2]], 20);
+    /// ```
+    pub fn vacant_slots(&mut self, n: usize) -> VacantSlots<'_, T, O, G, I> {
+        self.values.reserve(n);
+        VacantSlots {
+            arena: self,
+            remaining: n,
+        }
+    }
+
     /// Insert a new value into a [`GenericDenseArena`]
     pub fn insert<K: ArenaIndex<O, G>>(&mut self, value: T) -> K {
         self.insert_with(move |_| value)
     }
 
+    /// Try to insert a new value into a [`GenericDenseArena`]
+    ///
+    /// see [`GenericSparseArena::try_insert`](crate::generic_sparse::GenericSparseArena::try_insert)
+    pub fn try_insert<K: ArenaIndex<O, G>>(&mut self, value: T) -> Result<K, T> {
+        if self.tracker.can_insert() {
+            Ok(self.insert(value))
+        } else {
+            Err(value)
+        }
+    }
+
     /// Insert a new value that depends on the key into a [`GenericDenseArena`]
     pub fn insert_with<K: ArenaIndex<O, G>>(&mut self, value: impl FnOnce(K) -> T) -> K {
         let slot = self.vacant_slot();
@@ -123,6 +384,46 @@ where
         key
     }
 
+    /// Get the value associated with `existing` if it's a valid key, otherwise insert
+    /// a new value created by `make`
+    ///
+    /// Returns the key which is associated with the value, and a mutable reference to the value
+    ///
+    /// This is useful if you keep the keys in an external map, such as a `HashMap<Id, ArenaKey>`
+    pub fn get_or_insert_with_key<K: ArenaIndex<O, G>>(
+        &mut self,
+        existing: Option<K>,
+        make: impl FnOnce() -> T,
+    ) -> (K, &mut T) {
+        let key = match existing {
+            Some(key) if self.get(key).is_some() => key,
+            _ => self.insert(make()),
+        };
+
+        // SAFETY: the key was either just checked to be valid, or was just inserted
+        (key, unsafe { self.get_unchecked_mut(key) })
+    }
+
+    /// Returns true if the key is valid (in bounds, and has the correct generation)
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// arena.remove(a);
+    ///
+    /// assert!(arena.contains_key(b));
+    /// assert!(!arena.contains_key(a));
+    /// assert!(!arena.contains_key(5));
+    /// ```
+    #[inline]
+    pub fn contains_key<K: ArenaIndex<O, G>>(&self, key: K) -> bool {
+        self.tracker.contains_key(key)
+    }
+
     /// Get a reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or incorrect generation)
@@ -143,6 +444,56 @@ where
         Some(unsafe { self.values.get_unchecked_mut(index) })
     }
 
+    /// Check that every key is valid (in bounds, and has the correct generation)
+    ///
+    /// This stops at the first invalid key
+    pub fn all_valid<K: ArenaIndex<O, G>>(&self, keys: impl IntoIterator<Item = K>) -> bool {
+        keys.into_iter().all(|key| self.get(key).is_some())
+    }
+
+    /// Split the keys into the valid keys and the invalid keys, preserving their order
+    ///
+    /// see [`GenericDenseArena::all_valid`]
+    pub fn partition_valid<K: ArenaIndex<O, G>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> (Vec<K>, Vec<K>) {
+        keys.into_iter().partition(|&key| self.get(key).is_some())
+    }
+
+    /// A checksum of the keys and values in this arena, for detecting if two arenas
+    /// have the same contents
+    ///
+    /// see [`GenericSparseArena::content_checksum`](crate::generic_sparse::GenericSparseArena::content_checksum)
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena;
+    /// use ut_arena::generic_sparse::GenericSparseArena;
+    ///
+    /// let mut dense = GenericDenseArena::<char>::new();
+    /// let mut sparse = GenericSparseArena::<char>::new();
+    ///
+    /// for value in ['a', 'b', 'c'] {
+    ///     let _: usize = dense.insert(value);
+    ///     let _: usize = sparse.insert(value);
+    /// }
+    ///
+    /// // removing from a dense arena moves the last value, but that doesn't change the checksum
+    /// dense.remove(0usize);
+    /// sparse.remove(0usize);
+    /// assert_eq!(dense.content_checksum(), sparse.content_checksum());
+    /// ```
+    pub fn content_checksum(&self) -> u64
+    where
+        T: core::hash::Hash,
+    {
+        self.tracker
+            .keys::<crate::key::ArenaKey<usize, G>>()
+            .zip(&self.values)
+            .map(|(key, value)| crate::key_hash::checksum_entry(key, value))
+            .fold(0, u64::wrapping_add)
+    }
+
     /// Get a reference to the value associated with the key
     ///
     /// # Safety
@@ -204,6 +555,26 @@ where
         unsafe { self.remove_at(index) }
     }
 
+    /// Try to remove the element associated with the key, and check if the
+    /// [`GenericDenseArena`] is empty after the removal
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    pub fn try_remove_and_is_empty<K: ArenaIndex<O, G>>(&mut self, key: K) -> Option<(T, bool)> {
+        let value = self.try_remove(key)?;
+        Some((value, self.tracker.is_empty()))
+    }
+
+    /// Remove the element associated with the key, and check if the
+    /// [`GenericDenseArena`] is empty after the removal
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    pub fn remove_and_is_empty<K: ArenaIndex<O, G>>(&mut self, key: K) -> (T, bool) {
+        let value = self.remove(key);
+        (value, self.tracker.is_empty())
+    }
+
     /// Remove the element associated with the key without checking
     /// if the key is invalid or out of bounds
     ///
@@ -218,24 +589,268 @@ where
         unsafe { self.remove_at(index) }
     }
 
+    /// Swap the values associated with the two keys, both keys stay valid afterwards
+    ///
+    /// This swaps the values in [`GenericDenseArena::values`], and doesn't change the tracker
+    pub fn swap_values<K: ArenaIndex<O, G>>(&mut self, a: K, b: K) -> Result<(), SwapError> {
+        let a = self.tracker.get(a).ok_or(SwapError::InvalidA)?;
+        let b = self.tracker.get(b).ok_or(SwapError::InvalidB)?;
+
+        if a == b {
+            return Err(SwapError::SameKey);
+        }
+
+        self.values.swap(a, b);
+        Ok(())
+    }
+
+    /// Try to remove the element associated with the key, while preserving the
+    /// order of all other elements
+    ///
+    /// This is O(n) in the number of elements after the removed element
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    #[inline]
+    pub fn try_shift_remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> Option<T> {
+        let index = self.tracker.try_shift_remove(key)?;
+        Some(self.values.remove(index))
+    }
+
+    /// Remove the element associated with the key, while preserving the
+    /// order of all other elements
+    ///
+    /// This is O(n) in the number of elements after the removed element
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    #[inline]
+    pub fn shift_remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> T {
+        let index = self.tracker.shift_remove(key);
+        self.values.remove(index)
+    }
+
     /// The slice of values in this [`GenericDenseArena`]
     #[inline]
     pub fn values(&self) -> &[T] {
         &self.values
     }
 
+    /// Get an iterator over the keys and clones of the elements of this arena
+    ///
+    /// This is useful for taking snapshots of the arena without changing it
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    ///
+    /// let snapshot = arena.cloned_entries().collect::<Vec<(ArenaKey, _)>>();
+    /// assert_eq!(snapshot, [(a, 'a'), (b, 'b')]);
+    /// ```
+    #[inline]
+    pub fn cloned_entries<K: ArenaIndex<O, G>>(&self) -> ClonedEntries<'_, K, T, O, G, I>
+    where
+        T: Clone,
+    {
+        ClonedEntries {
+            keys: self.tracker.keys(),
+            values: self.values.iter(),
+        }
+    }
+
+    /// Get an iterator over the keys and values of this arena, sorted by key
+    ///
+    /// Unlike [`GenericDenseArena::values`], this doesn't depend on the order of removals,
+    /// which is useful for deterministic output. This allocates and sorts all the
+    /// entries up front, so it takes O(n log n) time.
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// let c: ArenaKey = arena.insert('c');
+    /// arena.remove(a);
+    ///
+    /// assert_eq!(arena.values(), ['c', 'b']);
+    ///
+    /// let sorted = arena.iter_sorted_by_key().collect::<Vec<(ArenaKey, _)>>();
+    /// assert_eq!(sorted, [(b, &'b'), (c, &'c')]);
+    /// ```
+    pub fn iter_sorted_by_key<K: ArenaIndex<O, G> + Ord>(&self) -> SortedByKey<'_, K, T> {
+        let mut entries = self
+            .tracker
+            .keys()
+            .zip(self.values.iter())
+            .collect::<Vec<(K, &T)>>();
+        entries.sort_unstable_by_key(|&(key, _)| key);
+
+        SortedByKey {
+            entries: entries.into_iter(),
+        }
+    }
+
     /// The mutable slice of values in this [`GenericDenseArena`]
     #[inline]
     pub fn values_mut(&mut self) -> &mut [T] {
         &mut self.values
     }
 
+    /// Call `f` on every value in this arena
+    ///
+    /// see [`GenericSparseArena::map_in_place`](crate::generic_sparse::GenericSparseArena::map_in_place)
+    #[inline]
+    pub fn map_in_place(&mut self, f: impl FnMut(&mut T)) {
+        self.values.iter_mut().for_each(f)
+    }
+
+    /// Call `f` on every value in this arena, and stop at the first error
+    ///
+    /// see [`GenericSparseArena::try_map_in_place`](crate::generic_sparse::GenericSparseArena::try_map_in_place)
+    #[inline]
+    pub fn try_map_in_place<E>(&mut self, f: impl FnMut(&mut T) -> Result<(), E>) -> Result<(), E> {
+        self.values.iter_mut().try_for_each(f)
+    }
+
+    /// Set if generations should wrap around instead of leaking slots when removing values
+    ///
+    /// see [`GenericDenseTracker::set_wrap_generations`]
+    #[inline]
+    pub fn set_wrap_generations(&mut self, wrap: bool) {
+        self.tracker.set_wrap_generations(wrap)
+    }
+
+    /// Get the insertion and removal counters of this arena
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.tracker.stats()
+    }
+
     /// The [`GenericDenseTracker`] that this [`GenericDenseArena`] uses
     #[inline]
     pub const fn tracker(&self) -> &GenericDenseTracker<O, G, I> {
         &self.tracker
     }
 
+    /// Get the key of the value at `position` in [`GenericDenseArena::values`]
+    ///
+    /// Returns [`None`] if the position is out of bounds
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: usize = arena.insert('a');
+    /// let b: usize = arena.insert('b');
+    /// arena.remove(a);
+    ///
+    /// let position = arena.values().iter().position(|&x| x == 'b').unwrap();
+    /// assert_eq!(arena.key_at_position(position), Some(b));
+    /// assert_eq!(arena.key_at_position::<usize>(1), None);
+    /// ```
+    #[inline]
+    pub fn key_at_position<K: ArenaIndex<O, G>>(&self, position: usize) -> Option<K> {
+        self.tracker.key_at_position(position)
+    }
+
+    /// Iterate over the keys and values of this arena, while collecting new values to insert
+    ///
+    /// see [`GenericSparseArena::iter_mut_deferred`](crate::generic_sparse::GenericSparseArena::iter_mut_deferred)
+    pub fn iter_mut_deferred<K: ArenaIndex<O, G>>(
+        &mut self,
+        mut f: impl FnMut(K, &mut T, &mut Deferred<T>),
+    ) -> Vec<K> {
+        let mut deferred = Deferred::new();
+
+        for (key, value) in self.tracker.keys().zip(&mut self.values) {
+            f(key, value, &mut deferred);
+        }
+
+        deferred
+            .into_values()
+            .into_iter()
+            .map(|value| self.insert(value))
+            .collect()
+    }
+
+    /// Create an iterator which removes and yields every element for which `f` returns true
+    ///
+    /// Elements are visited in storage order, and removed elements are replaced by the last
+    /// element (like [`GenericDenseArena::remove`]), which is visited next.
+    ///
+    /// see [`GenericSparseArena::extract_if`](crate::generic_sparse::GenericSparseArena::extract_if)
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let keys = (0..6).map(|i| arena.insert(i)).collect::<Vec<usize>>();
+    ///
+    /// let mut evens = arena.extract_if(|_: usize, value| *value % 2 == 0).collect::<Vec<_>>();
+    /// evens.sort();
+    /// assert_eq!(evens, [(keys[0], 0), (keys[2], 2), (keys[4], 4)]);
+    ///
+    /// assert_eq!(arena.values().len(), 3);
+    /// assert_eq!(arena[keys[5]], 5);
+    /// ```
+    pub fn extract_if<K: ArenaIndex<O, G>, F: FnMut(K, &mut T) -> bool>(
+        &mut self,
+        f: F,
+    ) -> ExtractIf<'_, K, T, F, O, G, I> {
+        ExtractIf {
+            arena: self,
+            index: 0,
+            f,
+            _key: PhantomData,
+        }
+    }
+
+    /// Reserve space for `additional` more values
+    ///
+    /// This reserves space in both the values and the [`GenericDenseTracker`],
+    /// see [`GenericDenseTracker::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
+        self.tracker.reserve(additional);
+    }
+
+    /// Shrink the capacity of the values to at most `min_capacity`, or the number of values
+    /// if that is larger
+    ///
+    /// This doesn't touch the [`GenericDenseTracker`], since it's slots are needed for the keys
+    ///
+    /// ```
+    /// use ut_arena::generic_dense::GenericDenseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let keys = (0..100).map(|i| arena.insert(i)).collect::<Vec<usize>>();
+    /// for key in &keys[10..] {
+    ///     arena.remove(*key);
+    /// }
+    ///
+    /// arena.shrink_values_to(20);
+    /// assert!(arena.values().len() == 10);
+    /// assert!(arena.values_capacity() >= 20);
+    /// assert!(arena.values_capacity() < 100);
+    /// ```
+    pub fn shrink_values_to(&mut self, min_capacity: usize) {
+        self.values.shrink_to(min_capacity)
+    }
+
+    /// The number of values this arena can hold without reallocating it's values
+    pub fn values_capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
     /// The mutable slice of values in this [`GenericDenseArena`]
     /// and the [`GenericDenseTracker`] that this [`GenericDenseArena`] uses
     ///
@@ -246,6 +861,99 @@ where
     }
 }
 
+impl<T, O, G: Generation, I: InternalIndex> VacantSlots<'_, T, O, G, I>
+where
+    O: core::fmt::Debug,
+{
+    /// Access the next vacant slot, or [`None`] if all the slots have been accessed
+    pub fn next_slot(&mut self) -> Option<VacantSlot<'_, T, O, G, I>> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        Some(self.arena.vacant_slot())
+    }
+
+    /// The number of vacant slots which haven't been accessed yet
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<K: ArenaIndex<O, G>, T: Clone, O: ?Sized, G: Generation, I: InternalIndex> Iterator
+    for ClonedEntries<'_, K, T, O, G, I>
+{
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next()?, self.values.next()?.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<K: ArenaIndex<O, G>, T: Clone, O: ?Sized, G: Generation, I: InternalIndex> ExactSizeIterator
+    for ClonedEntries<'_, K, T, O, G, I>
+{
+}
+
+impl<K: ArenaIndex<O, G>, T: Clone, O: ?Sized, G: Generation, I: InternalIndex> DoubleEndedIterator
+    for ClonedEntries<'_, K, T, O, G, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some((self.keys.next_back()?, self.values.next_back()?.clone()))
+    }
+}
+
+impl<K, T, F, O, G, I> Iterator for ExtractIf<'_, K, T, F, O, G, I>
+where
+    K: ArenaIndex<O, G>,
+    F: FnMut(K, &mut T) -> bool,
+    O: core::fmt::Debug,
+    G: Generation,
+    I: InternalIndex,
+{
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.arena.values.len() {
+            let key = self.arena.tracker.keys::<K>().nth(self.index)?;
+
+            if (self.f)(key, &mut self.arena.values[self.index]) {
+                // the last element is moved into self.index, so it will be visited next
+                return Some((key, self.arena.remove(key)));
+            }
+
+            self.index += 1;
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.arena.values.len() - self.index))
+    }
+}
+
+impl<'a, K, T> Iterator for SortedByKey<'a, K, T> {
+    type Item = (K, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, T> ExactSizeIterator for SortedByKey<'_, K, T> {}
+
+impl<K, T> DoubleEndedIterator for SortedByKey<'_, K, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
 impl<K: ArenaIndex<O, G>, O: ?Sized, G: Generation, I: InternalIndex, T> ops::Index<K>
     for GenericDenseArena<T, O, G, I>
 {
@@ -8,12 +8,13 @@ use core::{
     ops,
 };
 
+use alloc::vec::Vec;
 use ut_vec::{UtVec, UtVecElementIndex};
 
 use crate::{
     generation::{DefaultGeneration, Generation},
     internal_index::InternalIndex,
-    key::ArenaIndex,
+    key::{ArenaIndex, SwapError},
 };
 
 /// A [`GenericSparseArena`] is a small wrapper around a `Vec<(Generation, T)>`
@@ -32,8 +33,8 @@ use crate::{
 ///
 /// Each element of the list is a `Slot`, each slot can be in one of two states:
 /// * Empty: then it stores the generation and the next empty slot. If there are no other empty
-/// slots, then it holds the an index to one past the end of the list.
-/// For example, above slot4 points to 4, which is one past the end of the list.
+///   slots, then it holds the an index to one past the end of the list.
+///   For example, above slot4 points to 4, which is one past the end of the list.
 /// * Filled: then it stores the generation and the value it's filled with.
 ///
 /// The genration is responsible for tracking if a slot is empty or filled, so we don't need any
@@ -44,7 +45,7 @@ use crate::{
 ///
 /// On insertion,
 /// 1. if the free_list_head points to one pas the end
-///     a. push a new slot
+///    a. push a new slot
 /// 2. now the free_list_head points to a valid slot
 /// 3. insert the value into the slot pointed to by the free_list_head
 /// 4. increment the generation of the slot
@@ -52,20 +53,20 @@ use crate::{
 /// On access,
 /// 1. Check that the key is in bounds
 /// 2. check the generation of the indexed slot, and return an error if they fail
-///     a. if the key is [`usize`], or [`UtIndex`](ut_vec::UtIndex) then check if the genration
-///     represents a filled generation
-///     b. if the key is [`ArenaKey`](crate::key::ArenaKey), then check if the key's generation
-///     matches the slot's generation
+///    a. if the key is [`usize`], or [`UtIndex`](ut_vec::UtIndex) then check if the genration
+///    represents a filled generation
+///    b. if the key is [`ArenaKey`](crate::key::ArenaKey), then check if the key's generation
+///    matches the slot's generation
 /// 3. return the slot's value
 ///
 /// On removal,
 /// 1. do "On access,"
 /// 2. remove the value from the slot
 /// 3. try to increment the generation
-///     a. on success, write free_list_head to the slot, then set free_list_head to the index of
-///     the slot
-///     b. on failure write [`Generation::EMPTY`] as the generation and don't modify free_list_head
-///     (thus "leaking" the slot, as it can no longer be used at all).
+///    a. on success, write free_list_head to the slot, then set free_list_head to the index of
+///    the slot
+///    b. on failure write [`Generation::EMPTY`] as the generation and don't modify free_list_head
+///    (thus "leaking" the slot, as it can no longer be used at all).
 /// 4. return the value
 ///
 /// All of these operations are constant time, with low overhead.
@@ -79,6 +80,14 @@ pub struct GenericSparseArena<
     // this can be usize, since any smaller type won't make GenericArena any smaller
     // because we will round up to padding
     free_list_head: usize,
+    // a hint for the last slot in the free list, which is checked before it is used
+    // see GenericSparseArena::free_list_tail
+    free_list_tail: usize,
+    // every slot at or after this index has never been filled, see GenericSparseArena::push_value
+    fresh_slots_start: usize,
+    wrap_generations: bool,
+    #[cfg(feature = "metrics")]
+    stats: crate::stats::ArenaStats,
     slots: ut_vec::UtVec<Slot<T, G, I>, O>,
 }
 
@@ -116,6 +125,27 @@ union Slot<T, G: Generation, I: InternalIndex> {
     empty: EmptySlot<G, I>,
 }
 
+impl<T: Clone, G: Generation, I: InternalIndex> Clone for Slot<T, G, I> {
+    fn clone(&self) -> Self {
+        let generation = self.generation();
+
+        if generation.is_filled() {
+            Slot {
+                filled: ManuallyDrop::new(FilledSlot {
+                    generation,
+                    // SAFETY: the generation says this slot is filled
+                    value: unsafe { self.filled.value.clone() },
+                }),
+            }
+        } else {
+            Slot {
+                // SAFETY: the generation says this slot is empty
+                empty: unsafe { self.empty },
+            }
+        }
+    }
+}
+
 impl<T, G: Generation, I: InternalIndex> Drop for Slot<T, G, I> {
     fn drop(&mut self) {
         if core::mem::needs_drop::<T>() && self.generation().is_filled() {
@@ -126,6 +156,41 @@ impl<T, G: Generation, I: InternalIndex> Drop for Slot<T, G, I> {
     }
 }
 
+/// Values to insert into an arena after iterating over it, used by
+/// [`GenericSparseArena::iter_mut_deferred`] and
+/// [`GenericDenseArena::iter_mut_deferred`](crate::generic_dense::GenericDenseArena::iter_mut_deferred)
+pub struct Deferred<T> {
+    values: Vec<T>,
+}
+
+impl<T> Deferred<T> {
+    pub(crate) const fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    pub(crate) fn into_values(self) -> Vec<T> {
+        self.values
+    }
+
+    /// Insert a value into the arena once the iteration is finished
+    #[inline]
+    pub fn insert(&mut self, value: T) {
+        self.values.push(value);
+    }
+
+    /// The number of values that will be inserted
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if no values will be inserted
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
 /// a vacant slot into the [`GenericSparseArena`], created via [`GenericSparseArena::vacant_slot`]
 pub struct VacantSlot<
     'a,
@@ -135,9 +200,12 @@ pub struct VacantSlot<
     I: InternalIndex = usize,
 > {
     free_list_head: &'a mut usize,
+    fresh_slots_start: &'a mut usize,
     slot: &'a mut Slot<T, G, I>,
     owner: &'a O,
     next_empty_slot: usize,
+    #[cfg(feature = "metrics")]
+    stats: &'a mut crate::stats::ArenaStats,
 }
 
 impl<T, G: Generation, I: InternalIndex> Slot<T, G, I> {
@@ -146,13 +214,13 @@ impl<T, G: Generation, I: InternalIndex> Slot<T, G, I> {
         unsafe { self.generation }
     }
 
-    unsafe fn remove(&mut self, index: usize, free_list_head: &mut usize) -> T {
+    unsafe fn remove(&mut self, index: usize, free_list_head: &mut usize, wrap: bool) -> T {
         let generation = self.generation();
 
         // try to insert the slot into the free-list if the generation is not yet exhausted
         let (next_empty_slot, generation) =
             // SAFETY: the caller ensures that this slot is full, so calling try_empty is safe
-            if let Ok(generation) = unsafe { generation.try_empty() } {
+            if let Ok(generation) = unsafe { generation.try_empty_with(wrap) } {
                 let next_empty_slot = core::mem::replace(free_list_head, index);
 
                 (next_empty_slot, generation)
@@ -213,8 +281,14 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> VacantSlot<'_, T, O, G, I> {
         // and it's not possible to call [`Self::insert`] multiple times
         unsafe { slot.generation = slot.generation.fill() }
 
+        let index = *self.free_list_head;
+        *self.fresh_slots_start = (*self.fresh_slots_start).max(index + 1);
+
         // update the next_empty_slot to point to the slot after the next slot
         *self.free_list_head = self.next_empty_slot;
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_insert();
     }
 }
 
@@ -223,9 +297,668 @@ impl<T, G: Generation, I: InternalIndex> GenericSparseArena<T, (), G, I> {
     pub const fn new() -> Self {
         Self {
             free_list_head: 0,
+            free_list_tail: 0,
+            fresh_slots_start: 0,
+            wrap_generations: false,
+            #[cfg(feature = "metrics")]
+            stats: crate::stats::ArenaStats::new(),
             slots: UtVec::new(),
         }
     }
+
+    /// Create a new [`GenericSparseArena`] with `capacity` empty slots
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::with_capacity(16);
+    /// let capacity = arena.capacity();
+    /// let first = arena.reuse_order().next();
+    ///
+    /// for i in 0..16 {
+    ///     let _: usize = arena.insert(i);
+    /// }
+    ///
+    /// assert_eq!(first, Some(0));
+    /// assert_eq!(arena.capacity(), capacity);
+    /// assert_eq!(arena.slot_count(), 16);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut arena = Self::new();
+        arena.reserve(capacity);
+        arena
+    }
+}
+
+impl<T, G: Generation, I: InternalIndex> GenericSparseArena<T, (), G, I> {
+    /// Create a [`GenericSparseArena`] where each value is placed in the slot at it's index
+    ///
+    /// # Panics
+    ///
+    /// If the indices aren't strictly increasing
+    #[cfg(feature = "from-slab")]
+    pub(crate) fn from_sorted_entries(entries: impl IntoIterator<Item = (usize, T)>) -> Self {
+        let mut arena = Self::new();
+
+        for (index, value) in entries {
+            assert!(
+                index >= arena.slots.len(),
+                "Tried to create an arena from entries that weren't sorted by their index"
+            );
+
+            arena.push_empty_slots_until(index);
+            let _: usize = arena.push_filled_slot(value);
+        }
+
+        arena.relink_free_list();
+        arena
+    }
+
+    /// Create a [`GenericSparseArena`] where each value is placed in the slot at it's index,
+    /// with the given generation
+    ///
+    /// The slots don't need to be sorted. All slots which aren't given are empty, and
+    /// put on the free list in ascending order. `capacity` is the number of slots to reserve
+    /// space for.
+    ///
+    /// This is meant for restoring an arena which was saved with the keys and values from
+    /// [`GenericSparseArena::iter`].
+    ///
+    /// # Stale keys
+    ///
+    /// Only the generations of filled slots are given, so every slot which isn't given
+    /// starts over at [`Generation::EMPTY`]. Once a value is inserted into one of those slots,
+    /// keys which were removed from that slot before saving may access the new value, even with
+    /// generations like [`DefaultGeneration`] which otherwise never reuse keys. So all keys to
+    /// removed values must be discarded before restoring the arena.
+    ///
+    /// # Safety
+    ///
+    /// The slots must be consistent with each other. Each generation must come from a key which
+    /// was handed out by an arena with the same generation type for the slot at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there are any duplicate indices, or any index is too large for `I`
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// let c: ArenaKey = arena.insert('c');
+    /// arena.remove(b);
+    ///
+    /// let saved = arena
+    ///     .iter::<ArenaKey>()
+    ///     .map(|(key, &value)| (key.index(), key.generation(), value))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// // SAFETY: the generations are from keys to these slots
+    /// let mut restored = unsafe { Arena::<char>::from_raw_slots(saved, 0) };
+    /// assert_eq!(restored[a], 'a');
+    /// assert_eq!(restored.get(b), None);
+    /// assert_eq!(restored[c], 'c');
+    ///
+    /// // b's slot starts over at the empty generation, so b is valid again after
+    /// // inserting into it's slot
+    /// let d: ArenaKey = restored.insert('d');
+    /// assert_eq!(d, b);
+    /// assert_eq!(restored.get(b), Some(&'d'));
+    /// ```
+    pub unsafe fn from_raw_slots(mut slots: Vec<(usize, G::Filled, T)>, capacity: usize) -> Self {
+        slots.sort_unstable_by_key(|&(index, _, _)| index);
+
+        let mut arena = Self::new();
+        arena.slots.reserve(capacity);
+
+        for (index, generation, value) in slots {
+            assert!(
+                index >= arena.slots.len(),
+                "Tried to create an arena from slots with duplicate indices"
+            );
+
+            let generation = G::from_filled(generation);
+            arena.push_empty_slots_until(index);
+            // the free list may point one past the end of the slots, so that must fit in I
+            I::from_usize(index + 1);
+
+            arena.slots.push(Slot {
+                filled: ManuallyDrop::new(FilledSlot { generation, value }),
+            });
+
+            #[cfg(feature = "metrics")]
+            arena.stats.record_insert();
+        }
+
+        // the slots which weren't given may have been filled before they were saved
+        arena.fresh_slots_start = arena.slots.len();
+        arena.relink_free_list();
+        arena
+    }
+
+    /// Remove all slots which were never filled from the end of the arena, and return how many
+    /// slots were removed
+    ///
+    /// Slots which were filled before keep their generation, even if they are empty or
+    /// exhausted, so keys to removed values stay invalid after the arena grows again. The free
+    /// list is rebuilt so that it only contains the remaining empty slots, in ascending order.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::with_capacity(4);
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// arena.remove(b);
+    ///
+    /// // only the two slots which were never filled are removed
+    /// assert_eq!(arena.trim(), 2);
+    /// assert_eq!(arena.trim(), 0);
+    /// assert_eq!(arena.slot_count(), 2);
+    ///
+    /// let c: ArenaKey = arena.insert('c');
+    /// let d: ArenaKey = arena.insert('d');
+    /// assert_eq!(c.index(), b.index());
+    /// assert_eq!(arena.get(b), None);
+    /// assert_eq!(arena[a], 'a');
+    /// assert_eq!(arena[c], 'c');
+    /// assert_eq!(arena[d], 'd');
+    /// ```
+    pub fn trim(&mut self) -> usize {
+        let old_len = self.slots.len();
+
+        // SAFETY: the owner is (), so there are no unchecked indices into the slots
+        let slots = unsafe { self.slots.as_mut_vec() };
+        slots.truncate(self.fresh_slots_start);
+
+        let removed = old_len - slots.len();
+        if removed != 0 {
+            self.relink_free_list();
+        }
+        removed
+    }
+}
+
+impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G, I> {
+    /// Push empty slots until there are `len` slots
+    ///
+    /// Each new slot points to the slot after it, so if the free list ended at the old length,
+    /// then the new slots are added to the end of the free list. Otherwise the free list must
+    /// be relinked afterwards.
+    fn push_empty_slots_until(&mut self, len: usize) {
+        while self.slots.len() < len {
+            self.free_list_tail = self.slots.len();
+            self.slots.push(Slot {
+                empty: EmptySlot {
+                    generation: G::EMPTY,
+                    next_empty_slot: I::from_usize(self.slots.len() + 1),
+                },
+            });
+        }
+    }
+
+    /// Make sure there are at least `additional` empty slots in the free list, by appending
+    /// new empty slots to this arena if needed
+    ///
+    /// So the next `additional` insertions will neither reallocate, nor push new slots.
+    /// Like [`Vec::reserve`], empty slots which are already in the arena are counted,
+    /// so calling this repeatedly doesn't add more slots.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// arena.reserve(10);
+    /// arena.reserve(10);
+    /// assert_eq!(arena.slot_count(), 10);
+    ///
+    /// let _a: usize = arena.insert('a');
+    /// arena.reserve(10);
+    /// assert_eq!(arena.slot_count(), 11);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the number of slots would exceed [`GenericSparseArena::max_capacity`]
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = additional - self.free_slot_count(additional);
+        let len = self.slots.len().saturating_add(additional);
+        // the free list may point one past the end of the slots, so that must fit in I
+        I::from_usize(len);
+
+        self.slots.reserve(additional);
+        // the free list always ends at the old length, so the new slots are added to the end
+        self.push_empty_slots_until(len);
+    }
+
+    /// Count the slots in the free list, stopping once `max` slots have been counted
+    fn free_slot_count(&self, max: usize) -> usize {
+        let slots = self.slots.as_slice();
+        let mut index = self.free_list_head;
+        let mut count = 0;
+
+        while count < max && index < slots.len() {
+            // SAFETY: the free list only contains indices of empty slots that are in bounds,
+            // and it ends one past the end of the slots
+            index = unsafe { slots.get_unchecked(index).empty }
+                .next_empty_slot
+                .to_usize();
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Rebuild the free list from scratch, so that it contains every empty slot in ascending order
+    ///
+    /// Exhausted slots are identified by pointing to themselves, and aren't added to the free list
+    fn relink_free_list(&mut self) {
+        let slots = self.slots.as_mut_slice();
+        let len = slots.len();
+        let mut next_empty_slot = len;
+        self.free_list_tail = len;
+
+        for (index, slot) in slots.iter_mut().enumerate().rev() {
+            if slot.generation().is_filled() {
+                continue;
+            }
+
+            // SAFETY: the generation says that the slot is empty
+            let slot = unsafe { &mut slot.empty };
+
+            if slot.next_empty_slot.to_usize() == index {
+                continue;
+            }
+
+            if next_empty_slot == len {
+                self.free_list_tail = index;
+            }
+
+            slot.next_empty_slot = I::from_usize(next_empty_slot);
+            next_empty_slot = index;
+        }
+
+        self.free_list_head = next_empty_slot;
+    }
+
+    /// The maximum number of slots this arena can hold, which is limited by `I`
+    ///
+    /// see [`InternalIndex::MAX_CAPACITY`]
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena;
+    /// use ut_arena::generation::DefaultGeneration;
+    ///
+    /// assert_eq!(GenericSparseArena::<char, (), DefaultGeneration, u8>::max_capacity(), 255);
+    /// assert_eq!(GenericSparseArena::<char>::max_capacity(), usize::MAX);
+    /// ```
+    #[inline]
+    pub const fn max_capacity() -> usize {
+        I::MAX_CAPACITY
+    }
+
+    /// The number of slots in this arena, both filled and empty
+    ///
+    /// Iterating over the arena takes time proportional to this, not the number of values
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::with_capacity(4);
+    /// let _a: usize = arena.insert('a');
+    /// assert_eq!(arena.slot_count(), 4);
+    ///
+    /// arena.trim();
+    /// assert_eq!(arena.slot_count(), 1);
+    /// ```
+    #[inline]
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The number of slots this arena can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Check if the slot at `index` can be emptied and put back on the free list
+    /// at least `times` more times before it's generation is exhausted
+    ///
+    /// This always returns true for generations which are never exhausted, like the wrapping
+    /// `gwN` generations, or [`NoGeneration`](crate::generation::NoGeneration). Returns false if
+    /// the index is out of bounds, or the slot has already been exhausted.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::generation::g8;
+    ///
+    /// let mut arena = Arena::<char, (), g8>::new();
+    /// let a: usize = arena.insert('a');
+    /// assert!(arena.slot_can_reuse(a, 127));
+    /// assert!(!arena.slot_can_reuse(a, 128));
+    ///
+    /// arena.remove(a);
+    /// let a: usize = arena.insert('a');
+    /// assert!(arena.slot_can_reuse(a, 126));
+    /// assert!(!arena.slot_can_reuse(a, 127));
+    /// ```
+    pub fn slot_can_reuse(&self, index: usize, times: usize) -> bool {
+        let Some(slot) = self.slots.as_slice().get(index) else {
+            return false;
+        };
+
+        let generation = slot.generation();
+
+        // SAFETY: the generation says that the slot is empty
+        if generation.is_empty() && unsafe { slot.empty.next_empty_slot.to_usize() } == index {
+            return false;
+        }
+
+        match generation.remaining_cycles(self.wrap_generations) {
+            None => true,
+            Some(remaining) => remaining >= times as u128,
+        }
+    }
+
+    /// Check that the free list is well formed
+    ///
+    /// Returns false if the free list has a cycle, contains a filled slot, or
+    /// points out of bounds. This can only happen if the arena was corrupted
+    /// through unsafe code, so it's a cheap check to assert after unsafe operations.
+    ///
+    /// This takes O(slots) time
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: usize = arena.insert('a');
+    /// let b: usize = arena.insert('b');
+    /// arena.remove(a);
+    /// arena.remove(b);
+    /// assert!(arena.free_list_is_valid());
+    /// ```
+    pub fn free_list_is_valid(&self) -> bool {
+        let slots = self.slots.as_slice();
+        let mut index = self.free_list_head;
+
+        for _ in 0..=slots.len() {
+            let Some(slot) = slots.get(index) else {
+                return index == slots.len();
+            };
+
+            if slot.generation().is_filled() {
+                return false;
+            }
+
+            // SAFETY: the generation says that the slot is empty
+            index = unsafe { slot.empty.next_empty_slot.to_usize() };
+        }
+
+        false
+    }
+
+    /// Get an iterator over the slot indices that future insertions will use, in order
+    ///
+    /// This yields the indices on the free list, followed by the indices past the end of the
+    /// arena, which are used once the free list is empty. This assumes that only
+    /// [`GenericSparseArena::insert`] is used, since [`GenericSparseArena::push_value`]
+    /// always uses the next index past the end.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: usize = arena.insert('a');
+    /// let b: usize = arena.insert('b');
+    /// let c: usize = arena.insert('c');
+    /// arena.remove(a);
+    /// arena.remove(c);
+    ///
+    /// let order = arena.reuse_order().take(4).collect::<Vec<_>>();
+    /// assert_eq!(order, [c, a, 3, 4]);
+    ///
+    /// for &index in &order {
+    ///     assert_eq!(arena.insert::<usize>('z'), index);
+    /// }
+    /// ```
+    pub fn reuse_order(&self) -> ReuseOrder<'_, T, G, I> {
+        ReuseOrder {
+            slots: self.slots.as_slice(),
+            next: self.free_list_head,
+        }
+    }
+}
+
+/// Clones every slot, including the empty slots and their generations
+///
+/// So every key which is valid in this arena is also valid in the clone, and
+/// the free list is preserved, so both arenas will hand out the same keys on insertion.
+///
+/// This is only implemented for the `()` owner, since cloning a unique owner would
+/// allow keys from one arena to be used in another.
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<String>::new();
+/// let a: ArenaKey = arena.insert("a".to_string());
+/// let b: ArenaKey = arena.insert("b".to_string());
+/// arena.remove(a);
+///
+/// let mut clone = arena.clone();
+/// assert_eq!(clone[b], "b");
+/// assert!(!clone.contains_key(a));
+///
+/// clone[b].push('!');
+/// assert_eq!(arena[b], "b");
+/// assert_eq!(clone[b], "b!");
+///
+/// let c: ArenaKey = arena.insert("c".to_string());
+/// let d: ArenaKey = clone.insert("d".to_string());
+/// assert_eq!(c, d);
+/// ```
+impl<T: Clone, G: Generation, I: InternalIndex> Clone for GenericSparseArena<T, (), G, I> {
+    fn clone(&self) -> Self {
+        Self {
+            free_list_head: self.free_list_head,
+            free_list_tail: self.free_list_tail,
+            fresh_slots_start: self.fresh_slots_start,
+            wrap_generations: self.wrap_generations,
+            #[cfg(feature = "metrics")]
+            stats: self.stats,
+            slots: self.slots.clone(),
+        }
+    }
+}
+
+/// The serialized representation of a [`Slot`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Slot")]
+enum SlotRepr<T, G> {
+    Empty {
+        generation: G,
+        next_empty_slot: usize,
+    },
+    Filled {
+        generation: G,
+        value: T,
+    },
+}
+
+/// The serialized representation of a [`GenericSparseArena`]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "GenericSparseArena")]
+struct ArenaRepr<T, G> {
+    free_list_head: usize,
+    wrap_generations: bool,
+    slots: Vec<SlotRepr<T, G>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, G: Generation + serde::Serialize, I: InternalIndex> serde::Serialize
+    for Slot<T, G, I>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let generation = self.generation();
+
+        let slot = if generation.is_filled() {
+            SlotRepr::Filled {
+                generation,
+                // SAFETY: the generation says this slot is filled
+                value: unsafe { &self.filled.value },
+            }
+        } else {
+            SlotRepr::Empty {
+                generation,
+                // SAFETY: the generation says this slot is empty
+                next_empty_slot: unsafe { self.empty }.next_empty_slot.to_usize(),
+            }
+        };
+
+        serde::Serialize::serialize(&slot, serializer)
+    }
+}
+
+/// Serializes every slot with it's generation, including empty and exhausted slots, along with
+/// the free list
+///
+/// So every key which is valid in this arena is also valid in the deserialized arena, and keys
+/// which were removed stay invalid. This is only implemented for the `()` owner, since
+/// deserializing a unique owner would allow keys from one arena to be used in another.
+///
+/// Deserializing checks that the generations match the slots, and that the free list only
+/// contains empty slots.
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey = arena.insert('a');
+/// let b: ArenaKey = arena.insert('b');
+/// arena.remove(a);
+///
+/// let json = serde_json::to_string(&arena).unwrap();
+/// let mut arena: Arena<char> = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(arena.get(a), None);
+/// assert_eq!(arena[b], 'b');
+///
+/// let c: ArenaKey = arena.insert('c');
+/// assert_eq!(c.index(), a.index());
+/// assert_eq!(arena.get(a), None);
+/// ```
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, G: Generation + serde::Serialize, I: InternalIndex> serde::Serialize
+    for GenericSparseArena<T, (), G, I>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut arena = serializer.serialize_struct("GenericSparseArena", 3)?;
+        arena.serialize_field("free_list_head", &self.free_list_head)?;
+        arena.serialize_field("wrap_generations", &self.wrap_generations)?;
+        arena.serialize_field("slots", self.slots.as_slice())?;
+        arena.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<
+        'de,
+        T: serde::Deserialize<'de>,
+        G: Generation + serde::Deserialize<'de>,
+        I: InternalIndex,
+    > serde::Deserialize<'de> for GenericSparseArena<T, (), G, I>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ArenaRepr::<T, G>::deserialize(deserializer)?;
+        Self::from_repr(repr).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, G: Generation, I: InternalIndex> GenericSparseArena<T, (), G, I> {
+    fn from_repr(repr: ArenaRepr<T, G>) -> Result<Self, &'static str> {
+        let len = repr.slots.len();
+        // the free list may point one past the end of the slots, so that must fit in I
+        I::try_from_usize(len).ok_or("too many slots for the index type")?;
+
+        let mut slots = Vec::with_capacity(len);
+
+        for slot in repr.slots {
+            slots.push(match slot {
+                SlotRepr::Empty {
+                    generation,
+                    next_empty_slot,
+                } => {
+                    if generation.is_filled() {
+                        return Err("found an empty slot with a filled generation");
+                    }
+
+                    Slot {
+                        empty: EmptySlot {
+                            generation,
+                            next_empty_slot: I::try_from_usize(next_empty_slot)
+                                .ok_or("too many slots for the index type")?,
+                        },
+                    }
+                }
+                SlotRepr::Filled { generation, value } => {
+                    if generation.is_empty() {
+                        return Err("found a filled slot with an empty generation");
+                    }
+
+                    Slot {
+                        filled: ManuallyDrop::new(FilledSlot { generation, value }),
+                    }
+                }
+            });
+        }
+
+        // inserting writes to the slot at the head of the free list without checking it,
+        // so the free list must only contain empty slots and end one past the last slot
+        let mut index = repr.free_list_head;
+        let mut tail = len;
+        let mut remaining = len;
+
+        while index != len {
+            let slot = slots
+                .get(index)
+                .filter(|slot| slot.generation().is_empty())
+                .ok_or("the free list must only contain empty slots")?;
+
+            remaining = remaining
+                .checked_sub(1)
+                .ok_or("the free list must not contain a cycle")?;
+
+            tail = index;
+            // SAFETY: the generation says this slot is empty
+            index = unsafe { slot.empty }.next_empty_slot.to_usize();
+        }
+
+        Ok(Self {
+            free_list_head: repr.free_list_head,
+            free_list_tail: tail,
+            // the serialized arena doesn't track which slots were filled, so assume all were
+            fresh_slots_start: len,
+            wrap_generations: repr.wrap_generations,
+            #[cfg(feature = "metrics")]
+            stats: crate::stats::ArenaStats::new(),
+            slots: UtVec::from_vec(slots),
+        })
+    }
 }
 
 impl<T, G: Generation, I: InternalIndex> Default for GenericSparseArena<T, (), G, I> {
@@ -234,6 +967,39 @@ impl<T, G: Generation, I: InternalIndex> Default for GenericSparseArena<T, (), G
     }
 }
 
+/// Collects the values into a new arena, and discards their keys
+///
+/// Like [`Vec`], the values are inserted in order, so the `n`th value is in the `n`th slot
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+///
+/// let mut arena: Arena<char> = "hello".chars().collect();
+/// assert_eq!(arena[1], 'e');
+///
+/// arena.extend(['!']);
+/// assert_eq!(arena.values().collect::<String>(), "hello!");
+/// ```
+impl<T, G: Generation, I: InternalIndex> FromIterator<T> for GenericSparseArena<T, (), G, I> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T, G: Generation, I: InternalIndex> Extend<T> for GenericSparseArena<T, (), G, I> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        self.slots.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            let _: usize = self.insert(value);
+        });
+    }
+}
+
 #[cfg(feature = "unique-types")]
 impl<T, O, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G, I> {
     /// Create a new [`GenericSparseArena`] with the given owner
@@ -243,6 +1009,11 @@ impl<T, O, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G, I> {
     {
         Self {
             free_list_head: 0,
+            free_list_tail: 0,
+            fresh_slots_start: 0,
+            wrap_generations: false,
+            #[cfg(feature = "metrics")]
+            stats: crate::stats::ArenaStats::new(),
             slots: UtVec::from_owner(owner),
         }
     }
@@ -251,6 +1022,134 @@ impl<T, O, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G, I> {
     pub fn owner(&self) -> &O {
         self.slots.owner()
     }
+
+    /// Get a mutable reference to the owner of this type's keys
+    ///
+    /// # Safety
+    ///
+    /// see [`UtVec::owner_mut`]
+    pub unsafe fn owner_mut(&mut self) -> &mut O {
+        // SAFETY: ensured by caller
+        unsafe { self.slots.owner_mut() }
+    }
+
+    /// Move all values to the start of a new arena with a new owner, removing all empty slots
+    ///
+    /// `remap` is called with the old key and the new key of each value, in order of the old keys.
+    ///
+    /// Compacting an arena in place would invalidate all of it's [`UtIndex`](ut_vec::UtIndex) keys,
+    /// and the owner can't detect that. Since the compacted arena has a different owner, the old
+    /// keys are rejected by the new arena, so this is safe even when using branded keys.
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_vec::UtIndex;
+    ///
+    /// let mut arena = Arena::<char, _>::with_owner(RuntimeUt::new());
+    /// let a: UtIndex<_> = arena.insert('a');
+    /// let b: UtIndex<_> = arena.insert('b');
+    /// let c: UtIndex<_> = arena.insert('c');
+    /// arena.remove(a);
+    ///
+    /// let mut remapped = Vec::new();
+    /// let arena = arena.compact_branded(RuntimeUt::new(), |old: UtIndex<_>, new: UtIndex<_>| {
+    ///     remapped.push((old.get(), new));
+    /// });
+    ///
+    /// assert_eq!(remapped[0].0, b.get());
+    /// assert_eq!(remapped[1].0, c.get());
+    /// assert_eq!(arena[remapped[0].1], 'b');
+    /// assert_eq!(arena[remapped[1].1], 'c');
+    /// assert_eq!(remapped[1].1.get(), 1);
+    /// ```
+    pub fn compact_branded<O2, K, K2>(
+        self,
+        owner: O2,
+        mut remap: impl FnMut(K, K2),
+    ) -> GenericSparseArena<T, O2, G, I>
+    where
+        O: unique_types::UniqueToken,
+        O2: unique_types::UniqueToken,
+        K: ArenaIndex<O, G>,
+        K2: ArenaIndex<O2, G>,
+    {
+        // SAFETY: the old owner is dropped at the end of this function, and is only used
+        // to create keys for `remap`, so it can't be used to index another vector
+        let (slots, old_owner) = unsafe { self.slots.into_parts() };
+
+        let mut arena = GenericSparseArena::with_owner(owner);
+        arena.wrap_generations = self.wrap_generations;
+        #[cfg(feature = "metrics")]
+        {
+            arena.stats = self.stats;
+        }
+
+        for (index, slot) in slots.into_iter().enumerate() {
+            let generation = slot.generation();
+
+            if generation.is_empty() {
+                continue;
+            }
+
+            let new_index = arena.slots.len();
+            // moving the whole slot keeps the generation, so this is still a filled slot
+            // and new_index <= index, so it fits in I
+            arena.slots.push(slot);
+
+            // SAFETY: the generation says this slot is filled, index was in bounds of the
+            // old slots, and new_index is in bounds of the new slots since we just pushed it
+            let (old_key, new_key) = unsafe {
+                let filled = generation.to_filled();
+                (
+                    K::new(index, &old_owner, filled),
+                    K2::new(new_index, arena.slots.owner(), filled),
+                )
+            };
+
+            remap(old_key, new_key);
+        }
+
+        arena.free_list_head = arena.slots.len();
+        arena.fresh_slots_start = arena.slots.len();
+        arena
+    }
+}
+
+#[cfg(feature = "unique-types")]
+impl<T, O: ?Sized + unique_types::UniqueToken, I: InternalIndex>
+    GenericSparseArena<T, O, crate::generation::NoGeneration, I>
+{
+    /// Get a reference to the value associated with the key, without checking if the slot is
+    /// filled
+    ///
+    /// The [`UtIndex`](ut_vec::UtIndex) already skips the bounds check, so this doesn't branch
+    /// at all in release mode.
+    ///
+    /// # Safety
+    ///
+    /// The slot at `key` must be filled
+    ///
+    /// ```
+    /// use ut_arena::generation::NoGeneration;
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_vec::UtIndex;
+    ///
+    /// unique_types::unique_lifetime!(lt);
+    /// let mut arena = Arena::<_, _, NoGeneration>::with_owner(lt);
+    /// let key: UtIndex<_> = arena.insert('a');
+    ///
+    /// // SAFETY: key was just inserted, and nothing was removed
+    /// assert_eq!(unsafe { arena.get_assume_filled(key) }, &'a');
+    /// ```
+    #[inline]
+    pub unsafe fn get_assume_filled(&self, key: ut_vec::UtIndex<O>) -> &T {
+        let slot = &self.slots[key];
+        debug_assert!(slot.generation().is_filled());
+
+        // SAFETY: the caller ensures that the slot is filled
+        unsafe { &slot.filled.value }
+    }
 }
 
 impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G, I> {
@@ -265,6 +1164,33 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         });
     }
 
+    /// Check if generations wrap around instead of leaking slots when removing values
+    ///
+    /// This is `false` by default
+    #[inline]
+    pub fn wraps_generations(&self) -> bool {
+        self.wrap_generations
+    }
+
+    /// Set if generations should wrap around instead of leaking slots when removing values
+    ///
+    /// This is passed to [`Generation::try_empty_with`], so it only affects generations
+    /// which support both, like [`HybridGeneration`](crate::generation::HybridGeneration).
+    /// This only affects removals after the flag has been set
+    #[inline]
+    pub fn set_wrap_generations(&mut self, wrap: bool) {
+        self.wrap_generations = wrap;
+    }
+
+    /// Get the insertion and removal counters of this arena
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.stats
+    }
+
     /// Access a vacant slot in the arena
     #[inline]
     pub fn vacant_slot(&mut self) -> VacantSlot<'_, T, O, G, I> {
@@ -282,33 +1208,159 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
             next_empty_slot: unsafe { slot.empty }.next_empty_slot.to_usize(),
             slot,
             free_list_head: &mut self.free_list_head,
+            fresh_slots_start: &mut self.fresh_slots_start,
             owner,
+            #[cfg(feature = "metrics")]
+            stats: &mut self.stats,
         }
     }
 
+    /// Push a new filled slot onto the end of the slot list, without updating the free list
+    #[inline]
+    fn push_filled_slot<K: ArenaIndex<O, G>>(&mut self, value: T) -> K {
+        let index = self.slots.len();
+        // the free list may point one past the end of the slots, so that must fit in I
+        I::from_usize(index + 1);
+
+        self.slots.push(Slot {
+            filled: ManuallyDrop::new(FilledSlot {
+                // SAFETY: G::EMPTY is guaranteed to be empty, so we can fill it
+                generation: unsafe { G::EMPTY.fill() },
+                value,
+            }),
+        });
+
+        self.fresh_slots_start = index + 1;
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_insert();
+
+        // SAFETY: G::EMPTY is guaranteed to be empty, so we can fill it
+        // and index is guaranteed to be a valid index, since we just pushed it
+        unsafe { K::new(index, self.slots.owner(), G::EMPTY.fill().to_filled()) }
+    }
+
     /// Insert a new value into a [`GenericSparseArena`]
+    ///
+    /// This will reuse empty slots if there are any, see [`GenericSparseArena::push_value`]
+    /// if you always want to append a new slot
     #[inline]
     pub fn insert<K: ArenaIndex<O, G>>(&mut self, value: T) -> K {
         if self.free_list_head == self.slots.len() {
-            self.slots.push(Slot {
-                filled: ManuallyDrop::new(FilledSlot {
-                    // SAFETY: G::EMPTY is guaranteed to be empty, so we can fill it
-                    generation: unsafe { G::EMPTY.fill() },
-                    value,
-                }),
-            });
-
-            let index = self.free_list_head;
+            let key = self.push_filled_slot(value);
             self.free_list_head += 1;
-
-            // SAFETY: G::EMPTY is guaranteed to be empty, so we can fill it
-            // and self.free_list_head is guaranteed to be a valid index
-            unsafe { K::new(index, self.slots.owner(), G::EMPTY.fill().to_filled()) }
+            key
         } else {
             self.insert_with(move |_| value)
         }
     }
 
+    /// Insert a new value into a new slot at the end of the [`GenericSparseArena`]
+    ///
+    /// Unlike [`GenericSparseArena::insert`], this never reuses slots that held a removed value.
+    /// This is useful for append-heavy workloads, which would otherwise take the slower slot
+    /// reuse path. Empty slots which were never filled, like the ones added by
+    /// [`GenericSparseArena::reserve`], are used if they are at the head of the free list.
+    ///
+    /// This is O(1), the end of the free list is tracked so it can be moved past the new slot.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: usize = arena.insert('a');
+    /// let _b: usize = arena.insert('b');
+    /// arena.remove(a);
+    ///
+    /// // push_value doesn't reuse the slot that 'a' was in
+    /// let c: usize = arena.push_value('c');
+    /// assert_eq!(c, 2);
+    ///
+    /// // but insert does
+    /// let d: usize = arena.insert('d');
+    /// assert_eq!(d, a);
+    ///
+    /// // reserved slots are used without pushing new slots
+    /// let mut arena = Arena::<u32>::with_capacity(16);
+    /// for i in 0..16 {
+    ///     let _: usize = arena.push_value(i);
+    /// }
+    /// assert_eq!(arena.slot_count(), 16);
+    /// ```
+    #[inline]
+    pub fn push_value<K: ArenaIndex<O, G>>(&mut self, value: T) -> K {
+        let len = self.slots.len();
+
+        if self.free_list_head == len {
+            let key = self.push_filled_slot(value);
+            self.free_list_head += 1;
+            key
+        } else if self.free_list_head >= self.fresh_slots_start {
+            self.insert_with(move |_| value)
+        } else {
+            // calculate this before pushing, so that we don't leave the
+            // free list in a broken state if this panics
+            let new_end = I::from_usize(len + 1);
+            let tail = self.free_list_tail();
+            let key = self.push_filled_slot(value);
+
+            let slots = self.slots.as_mut_slice();
+            // SAFETY: free_list_tail returns an empty slot in bounds, since the free list isn't
+            // empty
+            unsafe { slots.get_unchecked_mut(tail).empty.next_empty_slot = new_end };
+            key
+        }
+    }
+
+    /// Get the index of the last slot in the free list
+    ///
+    /// The free list must not be empty
+    #[inline]
+    fn free_list_tail(&mut self) -> usize {
+        let len = self.slots.len();
+
+        // the last slot in the free list is the only empty slot which points one past
+        // the end of the slots. Exhausted slots point to themselves, and all other
+        // empty slots are in the free list
+        let is_tail = self
+            .slots
+            .as_slice()
+            .get(self.free_list_tail)
+            .is_some_and(|slot| {
+                // SAFETY: the generation says the slot is empty
+                slot.generation().is_empty()
+                    && unsafe { slot.empty }.next_empty_slot.to_usize() == len
+            });
+
+        if !is_tail {
+            self.free_list_tail = self.find_free_list_tail();
+        }
+
+        self.free_list_tail
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn find_free_list_tail(&self) -> usize {
+        let slots = self.slots.as_slice();
+        let mut index = self.free_list_head;
+
+        loop {
+            debug_assert!(index < slots.len());
+            // SAFETY: the free list only contains indices of empty slots that are in bounds
+            // and we stop walking it before reaching the end of the list
+            let next_empty_slot = unsafe { slots.get_unchecked(index).empty }
+                .next_empty_slot
+                .to_usize();
+
+            if next_empty_slot == slots.len() {
+                return index;
+            }
+
+            index = next_empty_slot;
+        }
+    }
+
     /// Insert a new value that depends on the key into a [`GenericSparseArena`]
     #[inline]
     pub fn insert_with<K: ArenaIndex<O, G>>(&mut self, value: impl FnOnce(K) -> T) -> K {
@@ -318,6 +1370,71 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         key
     }
 
+    /// Returns true if a value can be inserted without running out of indices in `I`
+    #[inline]
+    pub(crate) fn can_insert(&self) -> bool {
+        self.free_list_head != self.slots.len()
+            || I::try_from_usize(self.free_list_head + 1).is_some()
+    }
+
+    /// Try to insert a new value into a [`GenericSparseArena`]
+    ///
+    /// Unlike [`GenericSparseArena::insert`], this returns the value back instead of
+    /// panicking if the internal index type `I` can't address any more slots. This is
+    /// useful for small index types like [`u8`], which can be used as bounded tables.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena;
+    /// use ut_arena::generation::DefaultGeneration;
+    ///
+    /// let mut arena = GenericSparseArena::<u32, (), DefaultGeneration, u8>::new();
+    ///
+    /// let mut count = 0;
+    /// while let Ok(_) = arena.try_insert::<usize>(count) {
+    ///     count += 1;
+    /// }
+    ///
+    /// assert_eq!(count, 255);
+    /// assert_eq!(arena.try_insert::<usize>(1000), Err(1000));
+    ///
+    /// // removing a value frees up a slot to reuse
+    /// arena.remove(10usize);
+    /// assert_eq!(arena.try_insert::<usize>(1000), Ok(10));
+    /// ```
+    #[inline]
+    pub fn try_insert<K: ArenaIndex<O, G>>(&mut self, value: T) -> Result<K, T> {
+        if self.can_insert() {
+            Ok(self.insert(value))
+        } else {
+            Err(value)
+        }
+    }
+
+    /// Returns true if the key is valid (in bounds, and has the correct generation)
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// arena.remove(a);
+    /// let c: ArenaKey = arena.insert('c');
+    ///
+    /// assert!(arena.contains_key(b));
+    /// assert!(arena.contains_key(c));
+    /// // a has the same index as c, but a stale generation
+    /// assert!(!arena.contains_key(a));
+    /// assert!(!arena.contains_key(2));
+    /// ```
+    #[inline]
+    pub fn contains_key<K: ArenaIndex<O, G>>(&self, key: K) -> bool {
+        self.slots
+            .get(key.to_index())
+            .is_some_and(|slot| key.matches_generation(slot.generation()))
+    }
+
     /// Get a reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or incorrect generation)
@@ -350,6 +1467,70 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         }
     }
 
+    /// Check that every key is valid (in bounds, and has the correct generation)
+    ///
+    /// This stops at the first invalid key
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// let c: ArenaKey = arena.insert('c');
+    /// assert!(arena.all_valid([a, b, c]));
+    ///
+    /// arena.remove(b);
+    /// assert!(!arena.all_valid([a, b, c]));
+    /// assert_eq!(arena.partition_valid([a, b, c]), (vec![a, c], vec![b]));
+    /// ```
+    pub fn all_valid<K: ArenaIndex<O, G>>(&self, keys: impl IntoIterator<Item = K>) -> bool {
+        keys.into_iter().all(|key| self.get(key).is_some())
+    }
+
+    /// Split the keys into the valid keys and the invalid keys, preserving their order
+    ///
+    /// see [`GenericSparseArena::all_valid`]
+    pub fn partition_valid<K: ArenaIndex<O, G>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> (Vec<K>, Vec<K>) {
+        keys.into_iter().partition(|&key| self.get(key).is_some())
+    }
+
+    /// A checksum of the keys and values in this arena, for detecting if two arenas
+    /// have the same contents
+    ///
+    /// This only depends on the index, generation and value of each filled slot, and not on
+    /// empty slots, the order of the free list or the platform. The same contents in a
+    /// [`GenericDenseArena`](crate::generic_dense::GenericDenseArena) give the same checksum.
+    ///
+    /// This is not a cryptographic hash, so it can't be used to detect deliberate tampering.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut a = Arena::<char>::new();
+    /// let mut b = Arena::<char>::new();
+    ///
+    /// let _: usize = a.insert('a');
+    /// let _: usize = a.insert('b');
+    /// let _: usize = b.insert('a');
+    /// assert_ne!(a.content_checksum(), b.content_checksum());
+    ///
+    /// let _: usize = b.insert('b');
+    /// assert_eq!(a.content_checksum(), b.content_checksum());
+    /// ```
+    pub fn content_checksum(&self) -> u64
+    where
+        T: core::hash::Hash,
+    {
+        self.iter::<crate::key::ArenaKey<usize, G>>()
+            .map(|(key, value)| crate::key_hash::checksum_entry(key, value))
+            .fold(0, u64::wrapping_add)
+    }
+
     /// Get a reference to the value associated with the key
     ///
     /// # Safety
@@ -394,6 +1575,126 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         unsafe { &mut slot.filled.value }
     }
 
+    /// Get the value associated with `existing` if it's a valid key, otherwise insert
+    /// a new value created by `make`
+    ///
+    /// Returns the key which is associated with the value, and a mutable reference to the value
+    ///
+    /// This is useful if you keep the keys in an external map, such as a `HashMap<Id, ArenaKey>`
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let (a, value) = arena.get_or_insert_with_key::<ArenaKey>(None, || 10);
+    /// *value += 1;
+    ///
+    /// let (b, value) = arena.get_or_insert_with_key(Some(a), || 20);
+    /// assert_eq!(a, b);
+    /// assert_eq!(*value, 11);
+    ///
+    /// arena.remove(a);
+    /// let (c, value) = arena.get_or_insert_with_key(Some(a), || 20);
+    /// assert_ne!(a, c);
+    /// assert_eq!(*value, 20);
+    /// ```
+    pub fn get_or_insert_with_key<K: ArenaIndex<O, G>>(
+        &mut self,
+        existing: Option<K>,
+        make: impl FnOnce() -> T,
+    ) -> (K, &mut T) {
+        let key = match existing {
+            Some(key) if self.get(key).is_some() => key,
+            _ => self.insert(make()),
+        };
+
+        // SAFETY: the key was either just checked to be valid, or was just inserted
+        (key, unsafe { self.get_unchecked_mut(key) })
+    }
+
+    /// Swap the values associated with the two keys, both keys stay valid afterwards
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::{ArenaKey, SwapError};
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    ///
+    /// assert_eq!(arena.swap_values(a, b), Ok(()));
+    /// assert_eq!(arena[a], 'b');
+    /// assert_eq!(arena[b], 'a');
+    /// assert_eq!(arena.swap_values(a, a), Err(SwapError::SameKey));
+    /// ```
+    pub fn swap_values<K: ArenaIndex<O, G>>(&mut self, a: K, b: K) -> Result<(), SwapError> {
+        if self.get(a).is_none() {
+            return Err(SwapError::InvalidA);
+        }
+
+        if self.get(b).is_none() {
+            return Err(SwapError::InvalidB);
+        }
+
+        let a = a.to_index().get_index();
+        let b = b.to_index().get_index();
+
+        if a == b {
+            return Err(SwapError::SameKey);
+        }
+
+        let (left, right) = self.slots.as_mut_slice().split_at_mut(a.max(b));
+        let a = &mut left[a.min(b)];
+        let b = &mut right[0];
+
+        // SAFETY: both keys were checked to be valid, so both slots are filled
+        unsafe { core::mem::swap(&mut a.filled.value, &mut b.filled.value) }
+
+        Ok(())
+    }
+
+    /// Get the value in the slot at the given index, ignoring the slot's generation
+    ///
+    /// This is meant for debugging stale keys, i.e. to see what is in the slot now. It
+    /// doesn't check generations at all, so the value may have been inserted long after
+    /// your key was invalidated. Don't use this for program logic, use
+    /// [`GenericSparseArena::get`] instead.
+    ///
+    /// Returns [`None`] if the index points to an empty slot, or is out of bounds
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// arena.remove(a);
+    /// assert_eq!(arena.peek_slot(a.index()), None);
+    ///
+    /// let _b: ArenaKey = arena.insert('b');
+    /// assert_eq!(arena.get(a), None);
+    /// assert_eq!(arena.peek_slot(a.index()), Some(&'b'));
+    /// ```
+    #[inline]
+    pub fn peek_slot(&self, index: usize) -> Option<&T> {
+        let slot = self.slots.get(index)?;
+        if slot.generation().is_filled() {
+            // SAFETY: the generation says the slot is filled
+            Some(unsafe { &slot.filled.value })
+        } else {
+            None
+        }
+    }
+
+    /// Get the generation of the slot at `index`, regardless of if it is filled
+    ///
+    /// Returns None if the index is out of bounds
+    #[inline]
+    pub fn slot_generation(&self, index: usize) -> Option<G> {
+        self.slots.get(index).map(Slot::generation)
+    }
+
     /// Get the key associated with an index into the arena
     ///
     /// Returns [`None`] if the index points to an empty slot, or is out of bounds
@@ -452,10 +1753,13 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         if key.matches_generation(slot.generation()) {
             debug_assert!(slot.generation().is_filled());
 
+            #[cfg(feature = "metrics")]
+            self.stats.record_remove();
+
             // SAFETY: self.get ensures that the index is in bounds
             // we have checked that the genration is filled
             // and free_list_head always points to a valid empty index
-            Some(unsafe { slot.remove(index, &mut self.free_list_head) })
+            Some(unsafe { slot.remove(index, &mut self.free_list_head, self.wrap_generations) })
         } else {
             None
         }
@@ -474,10 +1778,13 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         key.assert_matches_generation(slot.generation());
         debug_assert!(slot.generation().is_filled());
 
+        #[cfg(feature = "metrics")]
+        self.stats.record_remove();
+
         // SAFETY: self.get ensures that the index is in bounds
         // we have checked that the genration is filled
         // and free_list_head always points to a valid empty index
-        unsafe { slot.remove(index, &mut self.free_list_head) }
+        unsafe { slot.remove(index, &mut self.free_list_head, self.wrap_generations) }
     }
 
     /// Remove the element associated with the key without checking
@@ -493,8 +1800,12 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         let slot = unsafe { self.slots.get_unchecked_mut(index) };
         debug_assert!(slot.generation().is_filled());
         let index = index.get_index();
+
+        #[cfg(feature = "metrics")]
+        self.stats.record_remove();
+
         // SAFETY: the caller ensures that the slot is filled
-        unsafe { slot.remove(index, &mut self.free_list_head) }
+        unsafe { slot.remove(index, &mut self.free_list_head, self.wrap_generations) }
     }
 
     /// Get an iterator over the keys and references to elements of this arena
@@ -507,6 +1818,55 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         }
     }
 
+    /// Get an iterator over the keys and references to elements in the slots within `range`
+    ///
+    /// The range is clamped to [`GenericSparseArena::slot_count`], and only the slots
+    /// in the range are visited.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let keys = (0..10).map(|i| arena.insert(i)).collect::<Vec<usize>>();
+    /// arena.remove(keys[4]);
+    ///
+    /// let window = arena.iter_range::<usize>(3..6).collect::<Vec<_>>();
+    /// assert_eq!(window, [(3, &3), (5, &5)]);
+    ///
+    /// assert_eq!(arena.iter_range::<usize>(8..100).count(), 2);
+    /// ```
+    #[inline]
+    pub fn iter_range<K: ArenaIndex<O, G>>(
+        &self,
+        range: ops::Range<usize>,
+    ) -> Iter<'_, K, T, O, G, I> {
+        let end = range.end.min(self.slots.len());
+        let start = range.start.min(end);
+
+        let mut slots = self.slots[..end].iter().enumerate();
+        if let Some(skip) = start.checked_sub(1) {
+            // skip the slots before the range, this is O(1) for slices
+            slots.nth(skip);
+        }
+
+        Iter {
+            slots,
+            owner: self.slots.owner(),
+            _key: PhantomData,
+        }
+    }
+
+    /// Get an iterator over the keys and clones of the elements of this arena
+    ///
+    /// This is useful for taking snapshots of the arena without changing it
+    #[inline]
+    pub fn cloned_entries<K: ArenaIndex<O, G>>(&self) -> ClonedEntries<'_, K, T, O, G, I>
+    where
+        T: Clone,
+    {
+        ClonedEntries { iter: self.iter() }
+    }
+
     /// Get an iterator over the keys and mut references to elements of this arena
     #[inline]
     pub fn iter_mut<K: ArenaIndex<O, G>>(&mut self) -> IterMut<'_, K, T, O, G, I> {
@@ -518,6 +1878,44 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
         }
     }
 
+    /// Iterate over the keys and values of this arena, while collecting new values to insert
+    ///
+    /// Values inserted into the [`Deferred`] aren't visited by this iteration, they are only
+    /// inserted after every existing value has been visited. Returns the keys of the inserted
+    /// values, in the order they were deferred.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let _: usize = arena.insert(1);
+    /// let _: usize = arena.insert(2);
+    ///
+    /// let spawned = arena.iter_mut_deferred(|_key: usize, value, deferred| {
+    ///     *value *= 10;
+    ///     deferred.insert(*value + 1);
+    /// });
+    ///
+    /// assert_eq!(spawned, [2, 3]);
+    /// assert_eq!(arena.values().copied().collect::<Vec<_>>(), [10, 20, 11, 21]);
+    /// ```
+    pub fn iter_mut_deferred<K: ArenaIndex<O, G>>(
+        &mut self,
+        mut f: impl FnMut(K, &mut T, &mut Deferred<T>),
+    ) -> Vec<K> {
+        let mut deferred = Deferred::new();
+
+        for (key, value) in self.iter_mut() {
+            f(key, value, &mut deferred);
+        }
+
+        deferred
+            .into_values()
+            .into_iter()
+            .map(|value| self.insert(value))
+            .collect()
+    }
+
     /// Get an iterator over the keys of this arena
     #[inline]
     pub fn keys<K: ArenaIndex<O, G>>(&self) -> Keys<'_, K, T, O, G, I> {
@@ -539,6 +1937,238 @@ impl<T, O: ?Sized, G: Generation, I: InternalIndex> GenericSparseArena<T, O, G,
             slots: self.slots.iter_mut(),
         }
     }
+
+    /// Call `f` on every value in this arena, skipping empty slots
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let a: usize = arena.insert(1);
+    /// let b: usize = arena.insert(2);
+    /// arena.remove(a);
+    ///
+    /// arena.map_in_place(|value| *value *= 10);
+    /// assert_eq!(arena[b], 20);
+    /// ```
+    #[inline]
+    pub fn map_in_place(&mut self, f: impl FnMut(&mut T)) {
+        self.values_mut().for_each(f)
+    }
+
+    /// Call `f` on every value in this arena, skipping empty slots, and stop at the first error
+    ///
+    /// Values after the one that failed aren't visited
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u8>::new();
+    /// let a: usize = arena.insert(100);
+    /// let b: usize = arena.insert(200);
+    /// let c: usize = arena.insert(50);
+    ///
+    /// let result = arena.try_map_in_place(|value| {
+    ///     *value = value.checked_mul(2).ok_or(*value)?;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(result, Err(200));
+    /// assert_eq!((arena[a], arena[b], arena[c]), (200, 200, 50));
+    /// ```
+    #[inline]
+    pub fn try_map_in_place<E>(&mut self, f: impl FnMut(&mut T) -> Result<(), E>) -> Result<(), E> {
+        self.values_mut().try_for_each(f)
+    }
+
+    /// Remove and drop all elements in this arena, while keeping it's slots and owner
+    ///
+    /// Every slot's generation is advanced just like in [`GenericSparseArena::remove`], so
+    /// keys from before the clear are invalid afterwards. Slots with exhausted generations
+    /// are leaked.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let a: ArenaKey = arena.insert(1);
+    /// let b: ArenaKey = arena.insert(2);
+    ///
+    /// arena.clear();
+    /// assert!(arena.get(a).is_none());
+    /// assert!(arena.get(b).is_none());
+    ///
+    /// let c: ArenaKey = arena.insert(3);
+    /// assert_eq!(c.index(), a.index());
+    /// assert_eq!(arena[c], 3);
+    /// assert_eq!(arena.slot_count(), 2);
+    /// ```
+    pub fn clear(&mut self) {
+        self.clear_from(0)
+    }
+
+    /// Remove and drop all elements in slots at or after `start`, then rebuild the free list
+    fn clear_from(&mut self, start: usize) {
+        for index in start..self.slots.len() {
+            let slot = &mut self.slots[index];
+
+            if slot.generation().is_empty() {
+                continue;
+            }
+
+            #[cfg(feature = "metrics")]
+            self.stats.record_remove();
+
+            // SAFETY: index is in bounds, the generation is filled,
+            // and free_list_head always points to a valid empty index
+            drop(unsafe { slot.remove(index, &mut self.free_list_head, self.wrap_generations) });
+        }
+
+        self.relink_free_list();
+    }
+
+    /// Create an iterator which removes and yields every element in this arena
+    ///
+    /// When the iterator is dropped, all elements which weren't yielded are dropped, and the
+    /// free list is rebuilt. If the iterator is leaked, then the elements which weren't yielded
+    /// stay in the arena.
+    ///
+    /// Keys to the removed elements are invalid afterwards, just like after
+    /// [`GenericSparseArena::remove`]
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: usize = arena.insert('a');
+    /// let b: usize = arena.insert('b');
+    /// let c: usize = arena.insert('c');
+    /// arena.remove(b);
+    ///
+    /// let mut drain = arena.drain::<usize>();
+    /// assert_eq!(drain.next(), Some((a, 'a')));
+    /// drop(drain);
+    ///
+    /// assert!(arena.get(c).is_none());
+    /// assert!(arena.values().next().is_none());
+    /// assert_eq!(arena.reuse_order().take(3).collect::<Vec<_>>(), [0, 1, 2]);
+    /// ```
+    pub fn drain<K: ArenaIndex<O, G>>(&mut self) -> Drain<'_, K, T, O, G, I> {
+        Drain {
+            arena: self,
+            index: 0,
+            _key: PhantomData,
+        }
+    }
+
+    /// Remove every element for which `f` returns false
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let keys = (0..6).map(|i| arena.insert(i)).collect::<Vec<usize>>();
+    ///
+    /// let mut removed = Vec::new();
+    /// arena.retain(|key: usize, value| {
+    ///     let keep = *value % 2 == 0;
+    ///     if !keep {
+    ///         removed.push(key);
+    ///     }
+    ///     keep
+    /// });
+    ///
+    /// assert_eq!(removed, [keys[1], keys[3], keys[5]]);
+    /// assert_eq!(arena.get(keys[2]), Some(&2));
+    /// assert_eq!(arena.get(keys[3]), None);
+    /// ```
+    pub fn retain<K: ArenaIndex<O, G>>(&mut self, mut f: impl FnMut(K, &mut T) -> bool) {
+        self.extract_if(|key, value| !f(key, value)).for_each(drop)
+    }
+
+    /// Create an iterator which removes and yields every element for which `f` returns true
+    ///
+    /// Elements for which `f` returns false stay in the arena. If the iterator is dropped
+    /// before it is exhausted, then all elements which weren't yielded stay in the arena.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<u32>::new();
+    /// let keys = (0..6).map(|i| arena.insert(i)).collect::<Vec<usize>>();
+    ///
+    /// let mut evens = arena.extract_if(|_: usize, value| *value % 2 == 0);
+    /// assert_eq!(evens.next(), Some((keys[0], 0)));
+    /// assert_eq!(evens.next(), Some((keys[2], 2)));
+    /// drop(evens);
+    ///
+    /// assert!(arena.get(keys[0]).is_none());
+    /// assert_eq!(arena.get(keys[3]), Some(&3));
+    /// assert_eq!(arena.get(keys[4]), Some(&4));
+    /// ```
+    pub fn extract_if<K: ArenaIndex<O, G>, F: FnMut(K, &mut T) -> bool>(
+        &mut self,
+        f: F,
+    ) -> ExtractIf<'_, K, T, F, O, G, I> {
+        ExtractIf {
+            arena: self,
+            index: 0,
+            f,
+            _key: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize, O: ?Sized, G: Generation, I: InternalIndex>
+    GenericSparseArena<T, O, G, I>
+{
+    /// Zeroize the element associated with the key, then remove and drop it
+    ///
+    /// Returns false if the key is invalid
+    pub fn try_remove_zeroize<K: ArenaIndex<O, G>>(&mut self, key: K) -> bool {
+        let Some(value) = self.get_mut(key) else {
+            return false;
+        };
+
+        value.zeroize();
+        self.try_remove(key);
+        true
+    }
+
+    /// Zeroize the element associated with the key, then remove and drop it
+    ///
+    /// Only the value is scrubbed. The generation and free list link left in the empty slot
+    /// never contain any data from the value, so they aren't sensitive.
+    ///
+    /// # Panics
+    ///
+    /// If the key is invalid
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    ///
+    /// let mut arena = Arena::<[u8; 4]>::new();
+    /// let key: usize = arena.insert(*b"pass");
+    ///
+    /// arena.remove_zeroize(key);
+    /// assert!(!arena.try_remove_zeroize(key));
+    /// ```
+    pub fn remove_zeroize<K: ArenaIndex<O, G>>(&mut self, key: K) {
+        self[key].zeroize();
+        self.remove(key);
+    }
+}
+
+/// Zeroizes every value in the arena in place, the keys stay valid
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize, O: ?Sized, G: Generation, I: InternalIndex> zeroize::Zeroize
+    for GenericSparseArena<T, O, G, I>
+{
+    fn zeroize(&mut self) {
+        self.values_mut().for_each(zeroize::Zeroize::zeroize)
+    }
 }
 
 impl<K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> ops::Index<K>
@@ -628,6 +2258,58 @@ pub struct Keys<
     iter: Iter<'a, K, T, O, G, I>,
 }
 
+/// An iterator over keys and clones of values in a [`GenericSparseArena`], created from
+/// [`GenericSparseArena::cloned_entries`]
+pub struct ClonedEntries<
+    'a,
+    K,
+    T,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    iter: Iter<'a, K, T, O, G, I>,
+}
+
+/// An iterator which removes all elements from a [`GenericSparseArena`], created from
+/// [`GenericSparseArena::drain`]
+pub struct Drain<
+    'a,
+    K,
+    T,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    arena: &'a mut GenericSparseArena<T, O, G, I>,
+    index: usize,
+    _key: PhantomData<fn() -> K>,
+}
+
+/// An iterator which removes elements from a [`GenericSparseArena`], created from
+/// [`GenericSparseArena::extract_if`]
+pub struct ExtractIf<
+    'a,
+    K,
+    T,
+    F,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    arena: &'a mut GenericSparseArena<T, O, G, I>,
+    index: usize,
+    f: F,
+    _key: PhantomData<fn() -> K>,
+}
+
+/// An iterator over the slot indices that future insertions will use, created from
+/// [`GenericSparseArena::reuse_order`]
+pub struct ReuseOrder<'a, T, G: Generation = DefaultGeneration, I: InternalIndex = usize> {
+    slots: &'a [Slot<T, G, I>],
+    next: usize,
+}
+
 impl<T, G: Generation, I: InternalIndex> Clone for Values<'_, T, G, I> {
     #[inline]
     fn clone(&self) -> Self {
@@ -713,6 +2395,93 @@ impl<'a, T, G: Generation, I: InternalIndex> DoubleEndedIterator for ValuesMut<'
     }
 }
 
+impl<K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> Iterator
+    for Drain<'_, K, T, O, G, I>
+{
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.arena.slots.len() {
+            let index = self.index;
+            self.index += 1;
+
+            if let Some(key) = self.arena.try_key_of::<K>(index) {
+                // SAFETY: try_key_of only returns keys to filled slots
+                return Some((key, unsafe { self.arena.remove_unchecked(key) }));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.arena.slots.len() - self.index))
+    }
+}
+
+impl<K, T, O: ?Sized, G: Generation, I: InternalIndex> Drop for Drain<'_, K, T, O, G, I> {
+    fn drop(&mut self) {
+        // every removal leaves the arena in a valid state, so if dropping a value panics
+        // the arena is still valid, the free list just isn't in ascending order
+        self.arena.clear_from(self.index);
+    }
+}
+
+impl<K, T, F, O, G, I> Iterator for ExtractIf<'_, K, T, F, O, G, I>
+where
+    K: ArenaIndex<O, G>,
+    F: FnMut(K, &mut T) -> bool,
+    O: ?Sized,
+    G: Generation,
+    I: InternalIndex,
+{
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.arena.slots.len() {
+            let index = self.index;
+            self.index += 1;
+
+            let Some(key) = self.arena.try_key_of::<K>(index) else {
+                continue;
+            };
+
+            // SAFETY: try_key_of only returns keys to filled slots
+            let value = unsafe { self.arena.get_unchecked_mut(key) };
+
+            if (self.f)(key, value) {
+                // SAFETY: the key still points to the same filled slot
+                return Some((key, unsafe { self.arena.remove_unchecked(key) }));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.arena.slots.len() - self.index))
+    }
+}
+
+impl<T, G: Generation, I: InternalIndex> Iterator for ReuseOrder<'_, T, G, I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next;
+
+        if let Some(slot) = self.slots.get(index) {
+            // SAFETY: the free list only contains empty slots
+            self.next = unsafe { slot.empty.next_empty_slot.to_usize() };
+        } else if index < I::MAX_CAPACITY {
+            self.next += 1;
+        } else {
+            return None;
+        }
+
+        Some(index)
+    }
+}
+
 impl<'a, K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> Iterator
     for Iter<'a, K, T, O, G, I>
 {
@@ -789,6 +2558,26 @@ impl<'a, K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> Dou
     }
 }
 
+impl<K: ArenaIndex<O, G>, T: Clone, O: ?Sized, G: Generation, I: InternalIndex> Iterator
+    for ClonedEntries<'_, K, T, O, G, I>
+{
+    type Item = (K, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, value)| (key, value.clone()))
+    }
+}
+
+impl<K: ArenaIndex<O, G>, T: Clone, O: ?Sized, G: Generation, I: InternalIndex> DoubleEndedIterator
+    for ClonedEntries<'_, K, T, O, G, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|(key, value)| (key, value.clone()))
+    }
+}
+
 impl<'a, K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> Iterator
     for Keys<'a, K, T, O, G, I>
 {
@@ -806,3 +2595,154 @@ impl<'a, K: ArenaIndex<O, G>, T, O: ?Sized, G: Generation, I: InternalIndex> Dou
         self.iter.next_back().map(|(key, _)| key)
     }
 }
+
+type JoinSlots<'a, A, B, G, I> = core::iter::Enumerate<
+    core::iter::Zip<core::slice::Iter<'a, Slot<A, G, I>>, core::slice::Iter<'a, Slot<B, G, I>>>,
+>;
+type JoinSlotsMut<'a, A, B, G, I> = core::iter::Enumerate<
+    core::iter::Zip<
+        core::slice::IterMut<'a, Slot<A, G, I>>,
+        core::slice::IterMut<'a, Slot<B, G, I>>,
+    >,
+>;
+
+/// Iterate over the elements which are in both arenas, created from [`join2`]
+pub struct Join2<
+    'a,
+    K,
+    A,
+    B,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    slots: JoinSlots<'a, A, B, G, I>,
+    owner: &'a O,
+    _key: PhantomData<fn() -> K>,
+}
+
+/// Iterate over the elements which are in both arenas, created from [`join2_mut`]
+pub struct Join2Mut<
+    'a,
+    K,
+    A,
+    B,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    slots: JoinSlotsMut<'a, A, B, G, I>,
+    owner: &'a O,
+    _key: PhantomData<fn() -> K>,
+}
+
+/// Iterate over all the slots which are filled in both arenas, and have the same generation
+///
+/// This is an inner join over the slots of the two arenas, which is useful when
+/// using multiple arenas as parallel stores of components for the same entities.
+///
+/// The keys are created with the owner of `a`, and slots past the end of the shorter
+/// arena are skipped.
+///
+/// ```
+/// use ut_arena::generic_sparse::{join2, GenericSparseArena as Arena};
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut names = Arena::<&str>::new();
+/// let mut ages = Arena::<u32>::new();
+///
+/// let alice: ArenaKey = names.insert("alice");
+/// let bob: ArenaKey = names.insert("bob");
+/// let carol: ArenaKey = names.insert("carol");
+///
+/// assert_eq!(ages.insert::<ArenaKey>(30), alice);
+/// assert_eq!(ages.insert::<ArenaKey>(40), bob);
+/// assert_eq!(ages.insert::<ArenaKey>(50), carol);
+/// ages.remove(bob);
+///
+/// let joined: Vec<(ArenaKey, _, _)> = join2(&names, &ages).collect();
+/// assert_eq!(joined, [(alice, &"alice", &30), (carol, &"carol", &50)]);
+/// ```
+pub fn join2<'a, K, A, B, O, O2, G, I>(
+    a: &'a GenericSparseArena<A, O, G, I>,
+    b: &'a GenericSparseArena<B, O2, G, I>,
+) -> Join2<'a, K, A, B, O, G, I>
+where
+    K: ArenaIndex<O, G>,
+    O: ?Sized,
+    O2: ?Sized,
+    G: Generation,
+    I: InternalIndex,
+{
+    Join2 {
+        slots: a.slots.iter().zip(b.slots.iter()).enumerate(),
+        owner: a.slots.owner(),
+        _key: PhantomData,
+    }
+}
+
+/// Iterate mutably over all the slots which are filled in both arenas, and have the same generation
+///
+/// see [`join2`] for details
+pub fn join2_mut<'a, K, A, B, O, O2, G, I>(
+    a: &'a mut GenericSparseArena<A, O, G, I>,
+    b: &'a mut GenericSparseArena<B, O2, G, I>,
+) -> Join2Mut<'a, K, A, B, O, G, I>
+where
+    K: ArenaIndex<O, G>,
+    O: ?Sized,
+    O2: ?Sized,
+    G: Generation,
+    I: InternalIndex,
+{
+    let (a_slots, owner) = a.slots.as_mut_slice_and_owner();
+    Join2Mut {
+        slots: a_slots.iter_mut().zip(b.slots.iter_mut()).enumerate(),
+        owner,
+        _key: PhantomData,
+    }
+}
+
+impl<'a, K: ArenaIndex<O, G>, A, B, O: ?Sized, G: Generation, I: InternalIndex> Iterator
+    for Join2<'a, K, A, B, O, G, I>
+{
+    type Item = (K, &'a A, &'a B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|(i, (a, b))| {
+            let generation = a.generation();
+            if generation.is_filled() && generation == b.generation() {
+                // SAFETY: Enumerate always yields valid indices into a
+                // and we have ensured that the slot's generation is filled
+                let key = unsafe { ArenaIndex::new(i, self.owner, generation.to_filled()) };
+                // SAFETY: the generations say both slots are filled
+                Some((key, unsafe { &a.filled.value }, unsafe { &b.filled.value }))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<'a, K: ArenaIndex<O, G>, A, B, O: ?Sized, G: Generation, I: InternalIndex> Iterator
+    for Join2Mut<'a, K, A, B, O, G, I>
+{
+    type Item = (K, &'a mut A, &'a mut B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|(i, (a, b))| {
+            let generation = a.generation();
+            if generation.is_filled() && generation == b.generation() {
+                // SAFETY: Enumerate always yields valid indices into a
+                // and we have ensured that the slot's generation is filled
+                let key = unsafe { ArenaIndex::new(i, self.owner, generation.to_filled()) };
+                // SAFETY: the generations say both slots are filled
+                Some((key, unsafe { &mut a.filled.value }, unsafe {
+                    &mut b.filled.value
+                }))
+            } else {
+                None
+            }
+        })
+    }
+}
@@ -8,6 +8,12 @@
 ///
 /// to_usize must give the exact usize that was passed to from_usize_unchecked
 pub unsafe trait InternalIndex: Copy + core::fmt::Debug + crate::seal::Seal {
+    /// The maximum number of slots an arena using this index type can hold
+    ///
+    /// This is `Self::MAX` (saturating at [`usize::MAX`]), since arenas need to be able
+    /// to represent one past the last slot.
+    const MAX_CAPACITY: usize;
+
     /// Tries to convert a usize to Self, panicking if it is too large
     ///
     /// # Panics
@@ -15,6 +21,9 @@ pub unsafe trait InternalIndex: Copy + core::fmt::Debug + crate::seal::Seal {
     /// x must be less or equal to than Self::MAX
     fn from_usize(x: usize) -> Self;
 
+    /// Tries to convert a usize to Self, returning None if it is too large
+    fn try_from_usize(x: usize) -> Option<Self>;
+
     /// Casts from usize to Self without checking if usize is too large
     ///
     /// # Safety
@@ -32,12 +41,23 @@ macro_rules! prim {
         impl crate::seal::Seal for $ty {}
         // SAFETY: TryInto ensures that the usize is in bounds of Self
         unsafe impl InternalIndex for $ty {
+            const MAX_CAPACITY: usize = if $ty::MAX as u128 > usize::MAX as u128 {
+                usize::MAX
+            } else {
+                $ty::MAX as usize
+            };
+
             #[inline]
             fn from_usize(x: usize) -> Self {
                 x.try_into()
                     .expect("tried to create a Arena with too many elements")
             }
 
+            #[inline]
+            fn try_from_usize(x: usize) -> Option<Self> {
+                x.try_into().ok()
+            }
+
             unsafe fn from_usize_unchecked(x: usize) -> Self {
                 debug_assert!(Self::try_from(x).is_ok());
                 x as Self
@@ -15,6 +15,12 @@ use crate::generation::{DefaultGeneration, Generation};
 ///
 /// The generation is a snapshot of the generation of the slot's genration
 /// If the slot is removed, then this key will become invalidated.
+///
+/// The `Align` parameter controls the minimum alignment of the key, it is never stored.
+/// By default keys are aligned to [`u64`], which allows the key to be
+/// loaded and compared as a single value on 64-bit targets. If you want
+/// smaller keys (for example with [`u16`] indices, or on 32-bit targets),
+/// then use a smaller alignment like [`u8`], or [`ArenaKeyPacked`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ArenaKey<I = usize, G: Generation = DefaultGeneration, Align = u64> {
     index: I,
@@ -22,18 +28,382 @@ pub struct ArenaKey<I = usize, G: Generation = DefaultGeneration, Align = u64> {
     _align: [Align; 0],
 }
 
+/// An [`ArenaKey`] without any extra alignment requirements
+///
+/// This makes the key only as large as it's index and generation require
+///
+/// ```
+/// use ut_arena::generation::g16;
+/// use ut_arena::key::{ArenaKey, ArenaKeyPacked};
+///
+/// assert_eq!(core::mem::size_of::<ArenaKey<u16, g16>>(), 8);
+/// assert_eq!(core::mem::size_of::<ArenaKeyPacked<u16, g16>>(), 4);
+/// ```
+pub type ArenaKeyPacked<I = usize, G = DefaultGeneration> = ArenaKey<I, G, u8>;
+
+/// Displays the key as `index#generation`
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey = arena.insert('a');
+/// arena.remove(a);
+/// let b: ArenaKey = arena.insert('b');
+///
+/// assert_eq!(a.to_string(), "0#1");
+/// assert_eq!(b.to_string(), "0#3");
+/// assert_eq!(format!("{:?}", b.generation()), "DefaultGenerationFilled(3)");
+/// ```
+impl<I: core::fmt::Display, G: Generation, _Align> core::fmt::Display for ArenaKey<I, G, _Align> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}#", self.index)?;
+        G::write_filled(self.generation, f)
+    }
+}
+
+/// Equal keys always hash equally.
+///
+/// If both the index and the filled generation fit in 32-bits, then they are packed
+/// into a single [`u64`] and hashed with one [`Hasher::write_u64`](core::hash::Hasher::write_u64)
+/// call. Otherwise the index and generation are hashed one after the other.
+///
+/// Since keys are handed out by the arena, and not chosen by users, there isn't
+/// much of a risk of HashDoS when keying maps by [`ArenaKey`]. So it should be fine to use a
+/// fast non-cryptographic hasher with them. With the `nohash` feature, small keys also
+/// implement [`nohash_hasher::IsEnabled`](https://docs.rs/nohash-hasher), so the packed
+/// [`u64`] can be used directly as the hash.
+///
+/// ```
+/// use core::hash::BuildHasher;
+/// use std::collections::hash_map::RandomState;
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey<u32> = arena.insert('a');
+/// let b: ArenaKey<u32> = arena.insert('b');
+/// let a2: ArenaKey<u32> = arena.key_of(0);
+///
+/// let state = RandomState::new();
+/// assert_eq!(a, a2);
+/// assert_eq!(state.hash_one(a), state.hash_one(a2));
+/// assert_ne!(state.hash_one(a), state.hash_one(b));
+/// ```
 impl<I: core::hash::Hash, G: Generation, _Align> core::hash::Hash for ArenaKey<I, G, _Align> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         crate::key_hash::hash(&self.index, self.generation, state)
     }
 }
 
-impl<I, G: Generation> ArenaKey<I, G> {
+/// Small keys hash with a single write, so they can be used with [`nohash_hasher`]
+///
+/// ```
+/// use nohash_hasher::BuildNoHashHasher;
+/// use std::collections::HashMap;
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey<u32> = arena.insert('a');
+///
+/// let mut map = HashMap::<_, _, BuildNoHashHasher<ArenaKey<u32>>>::default();
+/// map.insert(a, 10);
+/// assert_eq!(map[&a], 10);
+/// ```
+#[cfg(feature = "nohash")]
+impl<I: crate::key_hash::PackedHashPart, G: Generation, _Align> nohash_hasher::IsEnabled
+    for ArenaKey<I, G, _Align>
+where
+    G::Filled: crate::key_hash::PackedHashPart,
+{
+}
+
+/// Serializes the key as a tuple of it's index and filled generation
+///
+/// So a key which is serialized separately from it's arena still resolves once both are
+/// deserialized.
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: ArenaKey = arena.insert('a');
+/// arena.remove(a);
+/// let b: ArenaKey = arena.insert('b');
+///
+/// assert_eq!(serde_json::to_string(&b).unwrap(), "[0,3]");
+/// assert_eq!(serde_json::from_str::<ArenaKey>("[0,3]").unwrap(), b);
+///
+/// // filled generations are always odd
+/// assert!(serde_json::from_str::<ArenaKey>("[0,2]").is_err());
+/// ```
+#[cfg(feature = "serde")]
+impl<I: serde::Serialize, G: Generation, Align> serde::Serialize for ArenaKey<I, G, Align>
+where
+    G::Filled: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(&self.index, &self.generation), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, I: serde::Deserialize<'de>, G: Generation, Align> serde::Deserialize<'de>
+    for ArenaKey<I, G, Align>
+where
+    G::Filled: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (index, generation) = serde::Deserialize::deserialize(deserializer)?;
+
+        Ok(Self {
+            index,
+            generation,
+            _align: [],
+        })
+    }
+}
+
+/// Generates keys with an arbitrary index, and a generation that a slot
+/// could reach after being emptied and refilled a few times
+#[cfg(feature = "proptest")]
+impl<
+        I: proptest::arbitrary::Arbitrary,
+        G: Generation + 'static,
+        Align: core::fmt::Debug + 'static,
+    > proptest::arbitrary::Arbitrary for ArenaKey<I, G, Align>
+{
+    type Parameters = I::Parameters;
+    type Strategy =
+        proptest::strategy::Map<(I::Strategy, core::ops::Range<u32>), fn((I, u32)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        (
+            I::arbitrary_with(args),
+            0..crate::arbitrary::MAX_GENERATION_CYCLES,
+        )
+            .prop_map(|(index, cycles)| Self {
+                index,
+                generation: crate::arbitrary::filled_generation::<G>(cycles),
+                _align: [],
+            })
+    }
+}
+
+impl<I, G: Generation, Align> ArenaKey<I, G, Align> {
     /// Get the underlying index type of [`ArenaKey`]
     #[inline]
     pub fn index(self) -> I {
         self.index
     }
+
+    /// Get the filled generation of the slot when this key was created
+    #[inline]
+    pub fn generation(self) -> G::Filled {
+        self.generation
+    }
+
+    /// Check if this key's generation is newer than the other key's generation
+    ///
+    /// Returns [`None`] if the keys have different indices, since their generations
+    /// aren't related to each other.
+    ///
+    /// NOTE: this is only meaningful for generations that never wrap around, like the
+    /// `gN` types in [`generation`](crate::generation). For the wrapping `gwN` types, a newer key
+    /// may compare as older after the generation wraps around. And for
+    /// [`NoGeneration`](crate::generation::NoGeneration) this always returns `Some(false)`
+    /// for keys with the same index.
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    /// let b: ArenaKey = arena.insert('b');
+    /// arena.remove(a);
+    /// let c: ArenaKey = arena.insert('c');
+    ///
+    /// assert_eq!(c.generation_newer_than(&a), Some(true));
+    /// assert_eq!(a.generation_newer_than(&c), Some(false));
+    /// assert_eq!(a.generation_newer_than(&b), None);
+    /// ```
+    #[inline]
+    pub fn generation_newer_than(&self, other: &Self) -> Option<bool>
+    where
+        I: PartialEq,
+    {
+        if self.index == other.index {
+            Some(self.generation > other.generation)
+        } else {
+            None
+        }
+    }
+}
+
+/// Converts a `slotmap` key to an [`ArenaKey`]
+///
+/// `slotmap` keys are stored as a `u32` index and an odd `u32` version, and
+/// [`KeyData::as_ffi`](::slotmap::KeyData::as_ffi) packs them as `(version << 32) | index`.
+/// The index maps directly to the key's index, and the version maps directly to it's
+/// [`gw32`](crate::generation::gw32) generation, since both use odd numbers for filled slots.
+#[cfg(feature = "slotmap-compat")]
+impl From<::slotmap::KeyData> for ArenaKey<u32, crate::generation::gw32> {
+    fn from(data: ::slotmap::KeyData) -> Self {
+        let data = data.as_ffi();
+
+        Self {
+            index: data as u32,
+            generation: crate::generation::FilledGw32::from_slotmap_version((data >> 32) as u32),
+            _align: [],
+        }
+    }
+}
+
+/// Converts an [`ArenaKey`] to a `slotmap` key, using the same layout as the
+/// conversion from `slotmap` keys
+#[cfg(feature = "slotmap-compat")]
+impl From<ArenaKey<u32, crate::generation::gw32>> for ::slotmap::KeyData {
+    fn from(key: ArenaKey<u32, crate::generation::gw32>) -> Self {
+        let version = u64::from(key.generation.slotmap_version());
+        Self::from_ffi((version << 32) | u64::from(key.index))
+    }
+}
+
+/// The null key, which is the same as `slotmap`'s null key
+///
+/// The null key has index [`u32::MAX`], which is never in bounds of an arena
+/// since arenas can hold at most [`u32::MAX`] slots with a [`u32`] index
+#[cfg(feature = "slotmap-compat")]
+impl Default for ArenaKey<u32, crate::generation::gw32> {
+    fn default() -> Self {
+        ::slotmap::KeyData::default().into()
+    }
+}
+
+/// Allows using [`slotmap::ArenaKey`](crate::slotmap::ArenaKey) in `slotmap`'s collections
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena;
+/// use ut_arena::generation::gw32;
+/// use ut_arena::slotmap::ArenaKey;
+/// use slotmap::Key;
+///
+/// let mut map = GenericSparseArena::<char, (), gw32, u32>::new();
+/// let a: ArenaKey = map.insert('a');
+///
+/// let mut secondary = slotmap::SecondaryMap::new();
+/// secondary.insert(a, 10);
+/// assert_eq!(secondary[a], 10);
+///
+/// let mut upstream = slotmap::SlotMap::new();
+/// let b = upstream.insert('b');
+/// let b_data = b.data();
+///
+/// // the conversion is lossless
+/// let b = ArenaKey::from_slotmap_key(b);
+/// assert_eq!(b.data(), b_data);
+/// ```
+// SAFETY: all methods behave as if they were operating on the KeyData, since the
+// conversions to and from KeyData are lossless for keys created by either crate,
+// and both KeyData and ArenaKey compare by index first, then by version/generation
+#[cfg(feature = "slotmap-compat")]
+unsafe impl ::slotmap::Key for ArenaKey<u32, crate::generation::gw32> {
+    fn data(&self) -> ::slotmap::KeyData {
+        (*self).into()
+    }
+}
+
+#[cfg(feature = "slotmap-compat")]
+impl ArenaKey<u32, crate::generation::gw32> {
+    /// Convert any `slotmap` key into an [`ArenaKey`]
+    ///
+    /// The key is only valid for this crate's arenas if it has the same index and
+    /// generation as a key from that arena
+    pub fn from_slotmap_key<K: ::slotmap::Key>(key: K) -> Self {
+        key.data().into()
+    }
+}
+
+impl<G: Generation, Align> ArenaKey<usize, G, Align> {
+    /// Convert this key to a key with a [`u32`] index, preserving the generation and alignment
+    ///
+    /// Returns [`None`] if the index doesn't fit in a [`u32`]
+    ///
+    /// ```
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    ///
+    /// let mut arena = Arena::<char>::new();
+    /// let a: ArenaKey = arena.insert('a');
+    ///
+    /// let narrow: ArenaKey<u32> = a.try_narrow().unwrap();
+    /// assert_eq!(arena[narrow], 'a');
+    /// assert_eq!(narrow.widen(), a);
+    /// ```
+    #[inline]
+    pub fn try_narrow(self) -> Option<ArenaKey<u32, G, Align>> {
+        Some(ArenaKey {
+            index: self.index.try_into().ok()?,
+            generation: self.generation,
+            _align: [],
+        })
+    }
+}
+
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken, G: Generation, Align> ArenaKey<UtIndex<O>, G, Align> {
+    /// Create a key from a [`UtIndex`] and a filled generation
+    ///
+    /// The generation must be known separately, for example from another key to
+    /// the same slot, or from a key returned right after inserting into the slot.
+    /// If the generation doesn't match the slot's generation, then the key is just
+    /// rejected by the arena like any other stale key.
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+    /// use ut_arena::key::ArenaKey;
+    /// use ut_vec::UtIndex;
+    ///
+    /// let mut arena = Arena::<char, _>::with_owner(RuntimeUt::new());
+    /// let a: ArenaKey = arena.insert('a');
+    /// let index: UtIndex<_> = arena.key_of(a.index());
+    ///
+    /// let branded = ArenaKey::<_>::from_ut_index(index, a.generation());
+    /// assert_eq!(arena[branded], 'a');
+    ///
+    /// arena.remove(a);
+    /// let _b: ArenaKey = arena.insert('b');
+    /// assert_eq!(arena.get(branded), None);
+    /// ```
+    #[inline]
+    pub const fn from_ut_index(index: UtIndex<O>, generation: G::Filled) -> Self {
+        Self {
+            index,
+            generation,
+            _align: [],
+        }
+    }
+}
+
+impl<G: Generation, Align> ArenaKey<u32, G, Align> {
+    /// Convert this key to a key with a [`usize`] index, preserving the generation and alignment
+    ///
+    /// see [`ArenaKey::try_narrow`]
+    #[inline]
+    pub fn widen(self) -> ArenaKey<usize, G, Align> {
+        ArenaKey {
+            index: self.index as usize,
+            generation: self.generation,
+            _align: [],
+        }
+    }
 }
 
 #[cold]
@@ -67,6 +437,30 @@ pub(crate) fn access_empty_slot(index: usize) -> ! {
     panic!("Tried to access empy slot at index: {index}")
 }
 
+/// The error returned from `swap_values` on the arenas, such as
+/// [`GenericSparseArena::swap_values`](crate::generic_sparse::GenericSparseArena::swap_values)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapError {
+    /// The first key was invalid (out of bounds, or incorrect generation)
+    InvalidA,
+    /// The second key was invalid (out of bounds, or incorrect generation)
+    InvalidB,
+    /// Both keys point to the same element
+    SameKey,
+}
+
+impl core::fmt::Display for SwapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            SwapError::InvalidA => "tried to swap values with an invalid first key",
+            SwapError::InvalidB => "tried to swap values with an invalid second key",
+            SwapError::SameKey => "tried to swap a value with itself",
+        })
+    }
+}
+
+impl core::error::Error for SwapError {}
+
 /// A trait that manages access to arenas
 ///
 /// # Safety
@@ -74,7 +468,7 @@ pub(crate) fn access_empty_slot(index: usize) -> ! {
 /// * `to_index` must not change what index it returns
 /// * `matches_generation` should only succeed if the generation is filled
 /// * `assert_matches_generation` should only return normally if `matches_generation` would have
-///     returned true
+///   returned true
 pub unsafe trait ArenaIndex<O: ?Sized, G: Generation>: Copy {
     /// The underlying index type
     type UtIndex: UtVecElementIndex<O> + Copy;
@@ -235,3 +629,124 @@ unsafe impl<O: ?Sized + UniqueToken, G: Generation, _Align: Copy> ArenaIndex<O,
         }
     }
 }
+
+/// Define newtypes around keys, so that keys for different arenas can't be mixed up
+///
+/// Each newtype implements [`ArenaIndex`] by delegating to the key it wraps, which is
+/// [`ArenaKey`] by default, and can be converted to and from that key with [`From`].
+/// The wrapped key must implement `Debug`, `Copy`, `Ord` and `Hash`, like [`ArenaKey`] does.
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+/// use ut_arena::key::ArenaKey;
+///
+/// ut_arena::new_key_type! {
+///     /// The key of a player
+///     pub struct PlayerKey;
+///
+///     /// The key of an item, which uses a smaller key
+///     pub struct ItemKey(ArenaKey<u32>);
+/// }
+///
+/// struct Player {
+///     items: Vec<ItemKey>,
+/// }
+///
+/// let mut players = Arena::<Player>::new();
+/// let mut items = Arena::<&str>::new();
+///
+/// let sword: ItemKey = items.insert("sword");
+/// let player: PlayerKey = players.insert(Player { items: vec![sword] });
+///
+/// let player_items = &players[player].items;
+/// assert_eq!(items[player_items[0]], "sword");
+/// ```
+///
+/// Functions that take one kind of key can't be called with another kind
+///
+/// ```compile_fail
+/// # ut_arena::new_key_type! { struct PlayerKey; struct ItemKey; }
+/// fn remove_player(key: PlayerKey) {}
+///
+/// let mut items = ut_arena::generic_sparse::GenericSparseArena::<&str>::new();
+/// let sword: ItemKey = items.insert("sword");
+/// remove_player(sword);
+/// ```
+#[macro_export]
+macro_rules! new_key_type {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(($inner:ty))?;
+    )*) => {$(
+        $crate::new_key_type! {
+            @define [$($inner)?]
+            $(#[$meta])*
+            $vis struct $name
+        }
+    )*};
+    (
+        @define []
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident
+    ) => {
+        $crate::new_key_type! {
+            @define [$crate::key::ArenaKey]
+            $(#[$meta])*
+            $vis struct $name
+        }
+    };
+    (
+        @define [$inner:ty]
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident
+    ) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($inner);
+
+        impl ::core::convert::From<$inner> for $name {
+            #[inline]
+            fn from(key: $inner) -> Self {
+                Self(key)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $inner {
+            #[inline]
+            fn from(key: $name) -> Self {
+                key.0
+            }
+        }
+
+        // SAFETY: all methods delegate to the wrapped key, which upholds the requirements
+        unsafe impl<O: ?Sized, G: $crate::generation::Generation> $crate::key::ArenaIndex<O, G>
+            for $name
+        where
+            $inner: $crate::key::ArenaIndex<O, G>,
+        {
+            type UtIndex = <$inner as $crate::key::ArenaIndex<O, G>>::UtIndex;
+
+            #[inline]
+            unsafe fn new(index: usize, owner: &O, generation: G::Filled) -> Self {
+                // SAFETY: ensured by caller
+                Self(unsafe { <$inner as $crate::key::ArenaIndex<O, G>>::new(index, owner, generation) })
+            }
+
+            #[inline]
+            fn to_index(&self) -> Self::UtIndex {
+                self.0.to_index()
+            }
+
+            #[inline]
+            fn matches_generation(self, g: G) -> bool {
+                self.0.matches_generation(g)
+            }
+
+            #[inline]
+            fn assert_matches_generation(self, g: G) {
+                self.0.assert_matches_generation(g)
+            }
+        }
+    };
+}
@@ -51,3 +51,74 @@ impl Hasher for ExtractOneHasherU32 {
         self.count += 1;
     }
 }
+
+/// Hash a single live entry of an arena for a content checksum
+///
+/// Entries are hashed independently, so that the checksum can be built by adding them together
+/// in any order. This uses a fixed FNV-1a hasher, which writes all integers as little-endian
+/// [`u64`]s where possible, so the result doesn't depend on the platform.
+pub(crate) fn checksum_entry<K: Hash, T: Hash + ?Sized>(key: K, value: &T) -> u64 {
+    let mut hasher = Fnv1a(Fnv1a::OFFSET_BASIS);
+    key.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+}
+
+/// A type which hashes to at most one 32-bit write, so that [`ArenaKey`](crate::key::ArenaKey)s
+/// built from them hash with a single write
+#[cfg(feature = "nohash")]
+pub trait PackedHashPart {}
+
+#[cfg(feature = "nohash")]
+const _: () = {
+    use crate::generation::{
+        DefaultGenerationFilled, FilledG16, FilledG32, FilledG8, FilledGw16, FilledGw32, FilledGw8,
+    };
+
+    impl PackedHashPart for () {}
+    impl PackedHashPart for u8 {}
+    impl PackedHashPart for u16 {}
+    impl PackedHashPart for u32 {}
+    impl PackedHashPart for FilledG8 {}
+    impl PackedHashPart for FilledG16 {}
+    impl PackedHashPart for FilledG32 {}
+    impl PackedHashPart for FilledGw8 {}
+    impl PackedHashPart for FilledGw16 {}
+    impl PackedHashPart for FilledGw32 {}
+    impl PackedHashPart for DefaultGenerationFilled {}
+};
@@ -52,8 +52,8 @@
 //! There are three main strategies for ABA-hardening
 //!
 //! 1. increment a `uN` counter and if it ever reaches `uN::MAX`, then that slot is
-//!   exhausted. This slot will never contain any new elements in it.
-//!   This is handled by the `gN` types in [`generation`], such as [`g32`](generation::g32)
+//!    exhausted. This slot will never contain any new elements in it.
+//!    This is handled by the `gN` types in [`generation`], such as [`g32`](generation::g32)
 //!
 //! 2. increment a `uN` counter and if it ever reaches `uN::MAX`, just wrap around back to 0
 //!    NOTE: this looses the guarantee that all ArenaKeys are unique, but also allows reusing
@@ -61,8 +61,8 @@
 //!    This is handled by the `gwN` types in [`generation`], such as [`gw32`](generation::gw32)
 //!
 //! 3. flip a bool to indicate if the slot is empty or not.
-//!     NOTE: This doesn't handle the ABA problem at all. But can be useful if that's not actually
-//!     a problem for your domain. This is implemented by [`NoGeneration`](generation::NoGeneration)
+//!    NOTE: This doesn't handle the ABA problem at all. But can be useful if that's not actually
+//!    a problem for your domain. This is implemented by [`NoGeneration`](generation::NoGeneration)
 //!
 //! The default strategy ([`DefaultGeneration`](generation::DefaultGeneration)) currently uses
 //! [`gsize`](generation::gsize) as the backing generation type, but this may be changed in the future.
@@ -80,6 +80,13 @@
 //! [`unique-types`](unique_types), and [`UtIndex`](ut_vec::UtIndex) inside arena keys, then all
 //! bounds checks will be eliminated. see [`ut-vec`] for more details here.
 //!
+//! Each arena must own it's brand, so two arenas can't share one brand (for example by
+//! storing `&O`). A [`UtIndex`](ut_vec::UtIndex) skips the bounds check for any arena that owns
+//! it's brand, so a key from a longer arena could then be used to read past the end of a
+//! shorter one. To use one key across multiple component stores, either store all components
+//! of an entity together in a single branded arena, or use [`ArenaKey`](key::ArenaKey)s without
+//! a brand and iterate the stores together with [`join2`](generic_sparse::join2).
+//!
 //! ## Arena Types
 //!
 //! This crate provides two distinct arena types with different trade offs
@@ -89,8 +96,10 @@
 //! Sparse arenas don't store all elements contiguously, or track how many elements they have.
 //!
 //! They have a very fast access, insertion, and removal. All O(1) performance cost.
-//! Their memory footprint is also the same as `Vec<T>` + 1 usize if your elements are at least as
-//! large as `usize`. Making them extremely memory efficient.
+//! Their memory footprint is also the same as `Vec<T>` + 4 usizes if your elements are at least as
+//! large as `usize`. Making them extremely memory efficient. The extra usizes hold the head of the
+//! free list, a hint for the end of the free list, the start of the slots which were never filled,
+//! and the flag for wrapping generations (padded to a usize).
 //!
 //! The cost: iteration speed scales with the number of slots, not the number of
 //! elements. This is particularly bad if you iterate over the arena after removing many elements,
@@ -122,6 +131,7 @@ extern crate alloc;
 pub mod dense_tracker;
 pub mod generic_dense;
 pub mod generic_sparse;
+pub mod ordered_dense;
 
 pub mod generation;
 pub mod internal_index;
@@ -133,8 +143,15 @@ pub mod slab;
 pub mod dense_slotmap;
 pub mod slotmap;
 
+pub mod by_value;
+
 mod key_hash;
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "metrics")]
+pub mod stats;
+
 mod seal {
     pub trait Seal {}
 }
@@ -0,0 +1,189 @@
+//! A dense arena which keeps it's values in insertion order
+//!
+//! see [`OrderedDenseArena`] for details
+
+use core::ops;
+
+use crate::{
+    dense_tracker::GenericDenseTracker,
+    generation::{DefaultGeneration, Generation},
+    generic_dense::GenericDenseArena,
+    internal_index::InternalIndex,
+    key::ArenaIndex,
+};
+
+/// [`OrderedDenseArena`] is a [`GenericDenseArena`] which only removes elements
+/// via [`GenericDenseArena::shift_remove`]
+///
+/// This guarantees that [`OrderedDenseArena::ordered_values`] is always in insertion order,
+/// at the cost of O(n) removals.
+///
+/// ```
+/// use ut_arena::ordered_dense::OrderedDenseArena;
+/// use ut_arena::key::ArenaKey;
+///
+/// let mut arena = OrderedDenseArena::<char>::new();
+/// let a: ArenaKey = arena.insert('a');
+/// let b: ArenaKey = arena.insert('b');
+/// let c: ArenaKey = arena.insert('c');
+///
+/// assert_eq!(arena.remove(a), 'a');
+/// assert_eq!(arena.ordered_values(), ['b', 'c']);
+///
+/// let d: ArenaKey = arena.insert('d');
+/// assert_eq!(arena.ordered_values(), ['b', 'c', 'd']);
+/// assert_eq!(arena[b], 'b');
+/// assert_eq!(arena[c], 'c');
+/// assert_eq!(arena[d], 'd');
+/// ```
+pub struct OrderedDenseArena<
+    T,
+    O: ?Sized = (),
+    G: Generation = DefaultGeneration,
+    I: InternalIndex = usize,
+> {
+    arena: GenericDenseArena<T, O, G, I>,
+}
+
+impl<T, G: Generation, I: InternalIndex> OrderedDenseArena<T, (), G, I> {
+    /// Create a new [`OrderedDenseArena`]
+    pub const fn new() -> Self {
+        Self {
+            arena: GenericDenseArena::new(),
+        }
+    }
+}
+
+impl<T, G: Generation, I: InternalIndex> Default for OrderedDenseArena<T, (), G, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "unique-types")]
+impl<T, O, G: Generation, I: InternalIndex> OrderedDenseArena<T, O, G, I> {
+    /// Create a new [`OrderedDenseArena`] with the given owner
+    pub const fn with_owner(owner: O) -> Self
+    where
+        O: unique_types::UniqueToken,
+    {
+        Self {
+            arena: GenericDenseArena::with_owner(owner),
+        }
+    }
+
+    /// Get the owner of this type's keys
+    pub fn owner(&self) -> &O {
+        self.arena.owner()
+    }
+}
+
+impl<T, O, G: Generation, I: InternalIndex> OrderedDenseArena<T, O, G, I>
+where
+    O: core::fmt::Debug,
+{
+    /// Insert a new value at the end of the [`OrderedDenseArena`]
+    pub fn insert<K: ArenaIndex<O, G>>(&mut self, value: T) -> K {
+        self.arena.insert(value)
+    }
+
+    /// Insert a new value that depends on the key at the end of the [`OrderedDenseArena`]
+    pub fn insert_with<K: ArenaIndex<O, G>>(&mut self, value: impl FnOnce(K) -> T) -> K {
+        self.arena.insert_with(value)
+    }
+
+    /// Get a reference to the value associated with the key
+    ///
+    /// Returns None if the key is invalid (out of bounds, or incorrect generation)
+    #[inline]
+    pub fn get<K: ArenaIndex<O, G>>(&self, key: K) -> Option<&T> {
+        self.arena.get(key)
+    }
+
+    /// Get a mutable reference to the value associated with the key
+    ///
+    /// Returns None if the key is invalid (out of bounds, or incorrect generation)
+    #[inline]
+    pub fn get_mut<K: ArenaIndex<O, G>>(&mut self, key: K) -> Option<&mut T> {
+        self.arena.get_mut(key)
+    }
+
+    /// Try to remove the element associated with the key
+    ///
+    /// see [`GenericDenseArena::try_shift_remove`]
+    #[inline]
+    pub fn try_remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> Option<T> {
+        self.arena.try_shift_remove(key)
+    }
+
+    /// Remove the element associated with the key
+    ///
+    /// see [`GenericDenseArena::shift_remove`]
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    #[inline]
+    pub fn remove<K: ArenaIndex<O, G>>(&mut self, key: K) -> T {
+        self.arena.shift_remove(key)
+    }
+
+    /// The number of elements in the arena
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.arena.tracker().len()
+    }
+
+    /// Returns true if there are no elements in the arena
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.arena.tracker().is_empty()
+    }
+
+    /// The slice of values in this [`OrderedDenseArena`], in insertion order
+    #[inline]
+    pub fn ordered_values(&self) -> &[T] {
+        self.arena.values()
+    }
+
+    /// The [`GenericDenseTracker`] that this [`OrderedDenseArena`] uses
+    ///
+    /// The keys of the tracker are in the same order as [`OrderedDenseArena::ordered_values`]
+    #[inline]
+    pub const fn tracker(&self) -> &GenericDenseTracker<O, G, I> {
+        self.arena.tracker()
+    }
+
+    /// Get the underlying [`GenericDenseArena`]
+    #[inline]
+    pub const fn as_arena(&self) -> &GenericDenseArena<T, O, G, I> {
+        &self.arena
+    }
+
+    /// Convert this into the underlying [`GenericDenseArena`]
+    ///
+    /// This allows using [`GenericDenseArena::remove`] again, which doesn't
+    /// preserve insertion order
+    #[inline]
+    pub fn into_arena(self) -> GenericDenseArena<T, O, G, I> {
+        self.arena
+    }
+}
+
+impl<K: ArenaIndex<O, G>, O: ?Sized, G: Generation, I: InternalIndex, T> ops::Index<K>
+    for OrderedDenseArena<T, O, G, I>
+{
+    type Output = T;
+
+    fn index(&self, index: K) -> &Self::Output {
+        &self.arena[index]
+    }
+}
+
+impl<K: ArenaIndex<O, G>, O: ?Sized, G: Generation, I: InternalIndex, T> ops::IndexMut<K>
+    for OrderedDenseArena<T, O, G, I>
+{
+    fn index_mut(&mut self, index: K) -> &mut Self::Output {
+        &mut self.arena[index]
+    }
+}
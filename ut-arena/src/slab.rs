@@ -12,6 +12,7 @@ use crate::{
 ///
 /// [`Slab`] is instanciated as `GenericSparseArena<T, (), NoGeneration, usize>` and
 /// has an extra length field for compatiblity with the `slab` crate
+#[derive(Debug, Clone)]
 pub struct Slab<T> {
     len: usize,
     arena: GenericSparseArena<T, (), NoGeneration, usize>,
@@ -45,6 +46,50 @@ impl<T> Slab<T> {
         }
     }
 
+    /// Create a new [`Slab`] with space for `capacity` elements
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            len: 0,
+            arena: GenericSparseArena::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for `additional` more elements
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.arena.reserve(additional)
+    }
+
+    /// Convert a [`slab::Slab`] from the `slab` crate into a [`Slab`]
+    ///
+    /// All keys from the `slab` crate's [`Slab`](slab::Slab) refer to the same values
+    /// in the new [`Slab`]. Vacant slots past the last occupied slot are not preserved,
+    /// and the order in which vacant slots are reused may differ.
+    ///
+    /// ```
+    /// let mut upstream = slab::Slab::new();
+    /// let a = upstream.insert('a');
+    /// let b = upstream.insert('b');
+    /// let c = upstream.insert('c');
+    /// upstream.remove(b);
+    ///
+    /// let slab = ut_arena::slab::Slab::from_upstream(upstream);
+    /// assert_eq!(slab.len(), 2);
+    /// assert_eq!(slab[a], 'a');
+    /// assert_eq!(slab.get(b), None);
+    /// assert_eq!(slab[c], 'c');
+    /// ```
+    #[cfg(feature = "from-slab")]
+    pub fn from_upstream(slab: slab::Slab<T>) -> Self {
+        Self {
+            len: slab.len(),
+            arena: GenericSparseArena::from_sorted_entries(slab),
+        }
+    }
+
     /// Get the number of elements in the [`Slab`]
     pub const fn len(&self) -> usize {
         self.len
@@ -55,6 +100,14 @@ impl<T> Slab<T> {
         self.len == 0
     }
 
+    /// Get the insertion and removal counters of the [`Slab`]
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.arena.stats()
+    }
+
     /// Insert a new value into a [`Slab`]
     pub fn insert(&mut self, value: T) -> usize {
         self.len += 1;
@@ -82,6 +135,11 @@ impl<T> Slab<T> {
         self.arena.get(key)
     }
 
+    /// Returns true if the key is valid (in bounds, and the slot is filled)
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.arena.contains_key(key)
+    }
+
     /// Get a mutable reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or if the slot is empty)
@@ -133,6 +191,35 @@ impl<T> Slab<T> {
         value
     }
 
+    /// Try to remove the element associated with the key, and check if the
+    /// [`Slab`] is empty after the removal
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    pub fn try_remove_and_is_empty(&mut self, key: usize) -> Option<(T, bool)> {
+        let value = self.try_remove(key)?;
+        Some((value, self.is_empty()))
+    }
+
+    /// Remove the element associated with the key, and check if the
+    /// [`Slab`] is empty after the removal
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    ///
+    /// ```
+    /// let mut slab = ut_arena::slab::Slab::new();
+    /// let a = slab.insert('a');
+    /// let b = slab.insert('b');
+    ///
+    /// assert_eq!(slab.remove_and_is_empty(a), ('a', false));
+    /// assert_eq!(slab.remove_and_is_empty(b), ('b', true));
+    /// ```
+    pub fn remove_and_is_empty(&mut self, key: usize) -> (T, bool) {
+        let value = self.remove(key);
+        (value, self.is_empty())
+    }
+
     /// Remove the element associated with the key without checking
     /// if the key is invalid or out of bounds
     ///
@@ -171,6 +258,33 @@ impl<T> Slab<T> {
     }
 }
 
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects the values into a new [`Slab`], and discards their keys
+impl<T> FromIterator<T> for Slab<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T> Extend<T> for Slab<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            self.insert(value);
+        });
+    }
+}
+
 impl<T> core::ops::Index<usize> for Slab<T> {
     type Output = T;
 
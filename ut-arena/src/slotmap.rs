@@ -15,6 +15,7 @@ pub type ArenaKey = crate::key::ArenaKey<u32, gw32>;
 ///
 /// [`Slab`] is instanciated as `GenericSparseArena<T, (), NoGeneration, usize>` and
 /// has an extra length field for compatiblity with the `slab` crate
+#[derive(Debug, Clone)]
 pub struct SlotMap<T> {
     len: u32,
     arena: GenericSparseArena<T, (), gw32, u32>,
@@ -41,6 +42,21 @@ impl<T> VacantSlot<'_, T> {
 
 impl<T> SlotMap<T> {
     /// Create a new [`Slab`]
+    ///
+    /// ```
+    /// use ut_arena::slotmap::SlotMap;
+    ///
+    /// const EMPTY: SlotMap<u8> = const { SlotMap::new() };
+    /// const IS_EMPTY: bool = {
+    ///     let map = SlotMap::<u8>::new();
+    ///     let is_empty = map.is_empty() && map.len() == 0;
+    ///     core::mem::forget(map);
+    ///     is_empty
+    /// };
+    ///
+    /// assert!(IS_EMPTY);
+    /// assert!(EMPTY.is_empty());
+    /// ```
     pub const fn new() -> Self {
         Self {
             len: 0,
@@ -48,6 +64,60 @@ impl<T> SlotMap<T> {
         }
     }
 
+    /// Create a new [`SlotMap`] with space for `capacity` elements
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            len: 0,
+            arena: GenericSparseArena::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve space for `additional` more elements
+    ///
+    /// see [`GenericSparseArena::reserve`]
+    pub fn reserve(&mut self, additional: usize) {
+        self.arena.reserve(additional)
+    }
+
+    /// Convert a [`slotmap::SlotMap`] from the `slotmap` crate into a [`SlotMap`]
+    ///
+    /// The `slotmap` crate uses a different representation for its keys, so the values are
+    /// re-inserted into the new [`SlotMap`], and the returned [`SecondaryMap`](slotmap::SecondaryMap)
+    /// maps each of the old keys to the new [`ArenaKey`].
+    ///
+    /// Old keys that were already invalid before the conversion have no entry in the
+    /// returned map, so they stay invalid. However the new keys don't reuse the old
+    /// indices or generations, so old keys must be remapped before they are used
+    /// with the new [`SlotMap`].
+    ///
+    /// ```
+    /// let mut upstream = slotmap::SlotMap::new();
+    /// let a = upstream.insert('a');
+    /// let b = upstream.insert('b');
+    /// upstream.remove(a);
+    ///
+    /// let (map, remap) = ut_arena::slotmap::SlotMap::from_upstream(upstream);
+    /// assert_eq!(map.len(), 1);
+    /// assert!(remap.get(a).is_none());
+    /// assert_eq!(map.iter().next(), Some((remap[b], &'b')));
+    /// ```
+    #[cfg(feature = "from-slotmap")]
+    pub fn from_upstream<K: slotmap::Key>(
+        map: slotmap::SlotMap<K, T>,
+    ) -> (Self, slotmap::SecondaryMap<K, ArenaKey>) {
+        let mut this = Self::new();
+        let mut remap = slotmap::SecondaryMap::with_capacity(map.len());
+
+        for (key, value) in map {
+            this.len += 1;
+            remap.insert(key, this.arena.insert(value));
+        }
+
+        (this, remap)
+    }
+
     /// Get the number of elements in the [`Slab`]
     pub const fn len(&self) -> usize {
         self.len as usize
@@ -58,6 +128,14 @@ impl<T> SlotMap<T> {
         self.len == 0
     }
 
+    /// Get the insertion and removal counters of the [`SlotMap`]
+    ///
+    /// see [`ArenaStats`](crate::stats::ArenaStats)
+    #[cfg(feature = "metrics")]
+    pub fn stats(&self) -> crate::stats::ArenaStats {
+        self.arena.stats()
+    }
+
     /// Insert a new value into a [`Slab`]
     pub fn insert(&mut self, value: T) -> usize {
         self.len += 1;
@@ -85,6 +163,11 @@ impl<T> SlotMap<T> {
         self.arena.get(key)
     }
 
+    /// Returns true if the key is valid (in bounds, and the slot is filled)
+    pub fn contains_key(&self, key: usize) -> bool {
+        self.arena.contains_key(key)
+    }
+
     /// Get a mutable reference to the value associated with the key
     ///
     /// Returns None if the key is invalid (out of bounds, or if the slot is empty)
@@ -136,6 +219,26 @@ impl<T> SlotMap<T> {
         value
     }
 
+    /// Try to remove the element associated with the key, and check if the
+    /// [`SlotMap`] is empty after the removal
+    ///
+    /// Returns None if the key is invalid or out of bounds
+    pub fn try_remove_and_is_empty(&mut self, key: usize) -> Option<(T, bool)> {
+        let value = self.try_remove(key)?;
+        Some((value, self.is_empty()))
+    }
+
+    /// Remove the element associated with the key, and check if the
+    /// [`SlotMap`] is empty after the removal
+    ///
+    /// # Panics
+    ///
+    /// if the key is invalid or out of bounds
+    pub fn remove_and_is_empty(&mut self, key: usize) -> (T, bool) {
+        let value = self.remove(key);
+        (value, self.is_empty())
+    }
+
     /// Remove the element associated with the key without checking
     /// if the key is invalid or out of bounds
     ///
@@ -174,6 +277,40 @@ impl<T> SlotMap<T> {
     }
 }
 
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects the values into a new [`SlotMap`], and discards their keys
+///
+/// ```
+/// use ut_arena::slotmap::SlotMap;
+///
+/// let arena: SlotMap<char> = "hello".chars().collect();
+/// assert_eq!(arena.len(), 5);
+/// ```
+impl<T> FromIterator<T> for SlotMap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arena = Self::with_capacity(iter.size_hint().0);
+        arena.extend(iter);
+        arena
+    }
+}
+
+/// Inserts all the values, and discards their keys
+impl<T> Extend<T> for SlotMap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|value| {
+            self.insert(value);
+        });
+    }
+}
+
 impl<T> core::ops::Index<usize> for SlotMap<T> {
     type Output = T;
 
@@ -0,0 +1,68 @@
+//! Insertion and removal counters for arenas, enabled by the `metrics` feature
+//!
+//! see [`ArenaStats`] for details
+
+/// Counters for how many values have gone through an arena over it's lifetime
+///
+/// These are maintained by all insertion and removal paths of
+/// [`GenericSparseArena`](crate::generic_sparse::GenericSparseArena), and all other arenas are built
+/// on top of it, so they report the same counters.
+///
+/// All counters wrap around on overflow, which would take over 2^64 insertions.
+///
+/// ```
+/// use ut_arena::generic_sparse::GenericSparseArena as Arena;
+///
+/// let mut arena = Arena::<char>::new();
+/// let a: usize = arena.insert('a');
+/// let b: usize = arena.insert('b');
+/// arena.remove(a);
+/// let _: usize = arena.insert('c');
+///
+/// let stats = arena.stats();
+/// assert_eq!(stats.total_inserts, 3);
+/// assert_eq!(stats.total_removes, 1);
+/// assert_eq!(stats.peak_len, 2);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaStats {
+    /// The number of values that have been inserted
+    pub total_inserts: u64,
+    /// The number of values that have been removed
+    pub total_removes: u64,
+    /// The largest number of values that were in the arena at once
+    pub peak_len: u64,
+}
+
+impl ArenaStats {
+    pub(crate) const fn new() -> Self {
+        Self {
+            total_inserts: 0,
+            total_removes: 0,
+            peak_len: 0,
+        }
+    }
+
+    /// The number of values currently in the arena
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.total_inserts.wrapping_sub(self.total_removes)
+    }
+
+    /// Returns true if there are no values in the arena
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub(crate) fn record_insert(&mut self) {
+        self.total_inserts = self.total_inserts.wrapping_add(1);
+        self.peak_len = self.peak_len.max(self.len());
+    }
+
+    #[inline]
+    pub(crate) fn record_remove(&mut self) {
+        self.total_removes = self.total_removes.wrapping_add(1);
+    }
+}
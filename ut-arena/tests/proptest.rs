@@ -7,6 +7,7 @@ use rand::{
 };
 use ut_arena::{
     generation::g8, generic_dense::GenericDenseArena, generic_sparse::GenericSparseArena,
+    ordered_dense::OrderedDenseArena,
 };
 
 type ArenaKey = ut_arena::key::ArenaKey<usize, g8>;
@@ -77,6 +78,32 @@ impl Arena for GenericSparseArena<char, (), g8> {
     }
 }
 
+impl Arena for OrderedDenseArena<char, (), g8> {
+    fn new() -> Self {
+        Self::new()
+    }
+
+    fn insert(&mut self, value: char) -> ArenaKey {
+        self.insert(value)
+    }
+
+    fn remove(&mut self, key: ArenaKey) -> char {
+        self.remove(key)
+    }
+
+    fn get(&self, key: ArenaKey) -> Option<&char> {
+        self.get(key)
+    }
+
+    fn get_mut(&mut self, key: ArenaKey) -> Option<&mut char> {
+        self.get_mut(key)
+    }
+
+    fn try_remove(&mut self, key: ArenaKey) -> Option<char> {
+        self.try_remove(key)
+    }
+}
+
 fn test_arena<A: Arena>() {
     let mut arena = A::new();
     let mut map = rustc_hash::FxHashMap::default();
@@ -161,3 +188,8 @@ fn test_sparse_arena() {
 fn test_dense_arena() {
     test_arena::<GenericDenseArena<_, _, _, _>>();
 }
+
+#[test]
+fn test_ordered_dense_arena() {
+    test_arena::<OrderedDenseArena<_, _, _, _>>();
+}
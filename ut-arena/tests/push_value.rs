@@ -0,0 +1,53 @@
+use ut_arena::{
+    generation::{gw8, Generation, NoGeneration},
+    generic_sparse::GenericSparseArena,
+};
+
+/// Removed slots may get back to [`Generation::EMPTY`], but `push_value` must still skip them
+fn push_value_skips_removed_slots<G: Generation>() {
+    let mut arena = GenericSparseArena::<usize, (), G>::new();
+
+    for i in 0..4 {
+        let key: usize = arena.push_value(i);
+        assert_eq!(key, i);
+    }
+
+    arena.remove::<usize>(1);
+    arena.remove::<usize>(3);
+
+    for i in 4..8 {
+        let key: usize = arena.push_value(i);
+        assert_eq!(key, i);
+    }
+
+    assert_eq!(arena.slot_count(), 8);
+
+    // the removed slots are still reused by insert
+    let a: usize = arena.insert(8);
+    let b: usize = arena.insert(9);
+    assert_eq!([a, b], [3, 1]);
+}
+
+#[test]
+fn test_no_generation() {
+    push_value_skips_removed_slots::<NoGeneration>();
+}
+
+#[test]
+fn test_wrapping_generation() {
+    let mut arena = GenericSparseArena::<char, (), gw8>::new();
+    let a: usize = arena.insert('a');
+
+    // wrap the generation of the slot back around to empty
+    for _ in 0..127 {
+        arena.remove::<usize>(a);
+        arena.insert::<usize>('a');
+    }
+    arena.remove::<usize>(a);
+    assert_eq!(arena.slot_generation(a), Some(gw8::EMPTY));
+
+    let b: usize = arena.push_value('b');
+    assert_ne!(a, b);
+
+    push_value_skips_removed_slots::<gw8>();
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "serde")]
+
+use ut_arena::{
+    generation::{g8, gw8},
+    generic_dense::GenericDenseArena,
+    generic_sparse::GenericSparseArena,
+};
+
+type ArenaKey = ut_arena::key::ArenaKey<usize, g8>;
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_sparse_arena() {
+    let mut arena = GenericSparseArena::<String, (), g8>::new();
+    let a: ArenaKey = arena.insert("a".into());
+    let b: ArenaKey = arena.insert("b".into());
+    let c: ArenaKey = arena.insert("c".into());
+    arena.remove(a);
+    arena.remove(c);
+
+    // exhaust the generation of a slot, so it is leaked
+    let leaked: ArenaKey = arena.insert("d".into());
+    let mut d = leaked;
+    while d.index() == leaked.index() {
+        arena.remove(d);
+        d = arena.insert("d".into());
+    }
+
+    let [a, b, c, d, leaked] = round_trip(&[a, b, c, d, leaked]);
+    let mut restored = round_trip(&arena);
+
+    assert_eq!(restored.get(a), None);
+    assert_eq!(restored[b], "b");
+    assert_eq!(restored.get(c), None);
+    assert_eq!(restored[d], "d");
+    assert_eq!(restored.get(leaked), None);
+
+    // the free list is preserved, so both arenas hand out the same keys
+    for _ in 0..4 {
+        let key: ArenaKey = arena.insert("e".into());
+        assert_eq!(restored.insert::<ArenaKey>("e".into()), key);
+        assert_ne!(key.index(), leaked.index());
+    }
+
+    assert_eq!(restored.get(a), None);
+    assert_eq!(restored.get(c), None);
+}
+
+#[test]
+fn test_dense_arena() {
+    let mut arena = GenericDenseArena::<String, (), g8>::new();
+    let a: ArenaKey = arena.insert("a".into());
+    let b: ArenaKey = arena.insert("b".into());
+    let c: ArenaKey = arena.insert("c".into());
+    arena.remove(a);
+
+    let [a, b, c] = round_trip(&[a, b, c]);
+    let mut restored = round_trip(&arena);
+
+    assert_eq!(restored.get(a), None);
+    assert_eq!(restored[b], "b");
+    assert_eq!(restored[c], "c");
+    assert_eq!(restored.values(), arena.values());
+
+    let key: ArenaKey = arena.insert("d".into());
+    assert_eq!(restored.insert::<ArenaKey>("d".into()), key);
+    assert_eq!(restored.get(a), None);
+    assert_eq!(restored.remove(c), "c");
+}
+
+#[test]
+fn test_wrapping_generation() {
+    let mut arena = GenericSparseArena::<u8, (), gw8>::new();
+    let a: ut_arena::key::ArenaKey<usize, gw8> = arena.insert(0);
+    arena.remove(a);
+
+    let restored: GenericSparseArena<u8, (), gw8> = round_trip(&arena);
+    assert_eq!(restored.get(a), None);
+}
+
+#[test]
+fn test_invalid_sparse_arena() {
+    type Arena = GenericSparseArena<u8, (), g8>;
+
+    // the free list points to a filled slot
+    let json = r#"{"free_list_head":0,"wrap_generations":false,"slots":[{"Filled":{"generation":1,"value":0}}]}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_err());
+
+    // the free list has a cycle
+    let json = r#"{"free_list_head":0,"wrap_generations":false,"slots":[{"Empty":{"generation":0,"next_empty_slot":0}}]}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_err());
+
+    // an empty slot with a filled generation
+    let json = r#"{"free_list_head":1,"wrap_generations":false,"slots":[{"Empty":{"generation":1,"next_empty_slot":1}}]}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_err());
+
+    // a valid arena with a single empty slot
+    let json = r#"{"free_list_head":0,"wrap_generations":false,"slots":[{"Empty":{"generation":2,"next_empty_slot":1}}]}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_ok());
+}
+
+#[test]
+fn test_invalid_dense_arena() {
+    type Arena = GenericDenseArena<u8, (), g8>;
+
+    // both keys point to the same value
+    let json = r#"{"values":[0],"tracker":{"free_list_head":2,"wrap_generations":false,"slots":[{"Filled":{"generation":1,"value":0}},{"Filled":{"generation":1,"value":0}}]}}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_err());
+
+    // there are more values than keys
+    let json = r#"{"values":[0,1],"tracker":{"free_list_head":1,"wrap_generations":false,"slots":[{"Filled":{"generation":1,"value":0}}]}}"#;
+    assert!(serde_json::from_str::<Arena>(json).is_err());
+}
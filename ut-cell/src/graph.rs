@@ -0,0 +1,116 @@
+//! A graph whose nodes are [`UtCell`]s stored in an arena
+//!
+//! see [`Graph`] for details
+
+use core::ops;
+
+use ut_arena::{generic_sparse::GenericSparseArena, key::ArenaKey};
+
+use crate::{CellOwner, UtCell};
+
+/// The key of a node in a [`Graph`]
+pub type NodeKey = ArenaKey;
+
+/// A graph which owns all of it's nodes, with edges stored as [`NodeKey`]s
+///
+/// Building a graph out of `Arc<UtCell<..>>` leaks memory if there are any cycles,
+/// since each node in the cycle keeps the next one alive. Here, the [`Graph`] owns all
+/// of the nodes, and edges are just keys into the graph, so all nodes are dropped
+/// along with the [`Graph`], regardless of how they are connected.
+///
+/// Each node is wrapped in a [`UtCell`], so only a shared reference to the graph is
+/// needed to mutate nodes, and [`CellOwner::get_mut2`] can be used to mutate two nodes
+/// at once, for example to update both ends of an edge.
+///
+/// ```
+/// # use unique_types::lifetime::LifetimeUt;
+/// # use ut_cell::CellOwner;
+/// use ut_cell::graph::{Graph, NodeKey};
+///
+/// struct Node {
+///     value: u32,
+///     edges: Vec<NodeKey>,
+/// }
+///
+/// LifetimeUt::with(|mut owner| {
+///     let mut graph = Graph::new();
+///     let a = graph.insert(&owner, Node { value: 1, edges: Vec::new() });
+///     let b = graph.insert(&owner, Node { value: 2, edges: Vec::new() });
+///
+///     // create a cycle between a and b
+///     let (node_a, node_b) = owner.get_mut2(&graph[a], &graph[b]);
+///     node_a.edges.push(b);
+///     node_b.edges.push(a);
+///     node_a.value += node_b.value;
+///
+///     assert_eq!(owner.get(&graph[a]).value, 3);
+///     assert_eq!(owner.get(&graph[b]).edges, [a]);
+///
+///     // dropping the graph drops both nodes, even though they refer to each other
+///     drop(graph);
+/// });
+/// ```
+pub struct Graph<T, C: CellOwner + ?Sized> {
+    nodes: GenericSparseArena<UtCell<T, C>>,
+}
+
+impl<T, C: CellOwner + ?Sized> Graph<T, C> {
+    /// Create a new empty [`Graph`]
+    pub const fn new() -> Self {
+        Self {
+            nodes: GenericSparseArena::new(),
+        }
+    }
+
+    /// Insert a new node into the [`Graph`]
+    pub fn insert(&mut self, owner: &C, value: T) -> NodeKey {
+        self.nodes.insert(UtCell::new(owner, value))
+    }
+
+    /// Insert a new node that depends on it's own key into the [`Graph`]
+    pub fn insert_with(&mut self, owner: &C, value: impl FnOnce(NodeKey) -> T) -> NodeKey {
+        self.nodes.insert_with(|key| UtCell::new(owner, value(key)))
+    }
+
+    /// Get the cell of a node
+    ///
+    /// Returns None if the node has been removed
+    pub fn get(&self, key: NodeKey) -> Option<&UtCell<T, C>> {
+        self.nodes.get(key)
+    }
+
+    /// Get the value of a node, without an owner
+    ///
+    /// Returns None if the node has been removed
+    pub fn get_mut(&mut self, key: NodeKey) -> Option<&mut T> {
+        self.nodes.get_mut(key).map(UtCell::get_mut)
+    }
+
+    /// Remove a node from the [`Graph`]
+    ///
+    /// Any edges pointing to this node will no longer be valid keys into the [`Graph`]
+    ///
+    /// Returns None if the node has already been removed
+    pub fn remove(&mut self, key: NodeKey) -> Option<T> {
+        self.nodes.try_remove(key).map(UtCell::into_inner)
+    }
+
+    /// Get an iterator over the keys and cells of all nodes in the [`Graph`]
+    pub fn iter(&self) -> ut_arena::generic_sparse::Iter<'_, NodeKey, UtCell<T, C>> {
+        self.nodes.iter()
+    }
+}
+
+impl<T, C: CellOwner + ?Sized> Default for Graph<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: CellOwner + ?Sized> ops::Index<NodeKey> for Graph<T, C> {
+    type Output = UtCell<T, C>;
+
+    fn index(&self, key: NodeKey) -> &Self::Output {
+        &self.nodes[key]
+    }
+}
@@ -13,6 +13,9 @@
 //!
 //! This crate allows accessing interior mutable structures by utilizing unique types
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{cell::UnsafeCell, mem};
 
 use unique_types::{TrivialToken, UniqueType};
@@ -40,6 +43,9 @@ pub enum TryLoadAllError {
 #[doc(hidden)]
 pub mod load_all;
 
+#[cfg(feature = "graph")]
+pub mod graph;
+
 impl<T: ?Sized + UniqueType> CellOwner for T {}
 /// An extenion trait for [`UniqueType`] that allows accessing [`UtCell`]
 pub trait CellOwner: UniqueType {
@@ -75,7 +81,7 @@ pub trait CellOwner: UniqueType {
         a: &'a UtCell<T, Self>,
         b: &'a UtCell<U, Self>,
     ) -> (&'a mut T, &'a mut U) {
-        load_all!( self => a, b )
+        self.get_tuple_mut((a, b))
     }
 
     /// Get three mutable reference to a values in [`UtCell`]s
@@ -91,7 +97,7 @@ pub trait CellOwner: UniqueType {
         b: &'a UtCell<U, Self>,
         c: &'a UtCell<V, Self>,
     ) -> (&'a mut T, &'a mut U, &'a mut V) {
-        load_all!( self => a, b, c )
+        self.get_tuple_mut((a, b, c))
     }
 
     /// Get four mutable reference to a values in [`UtCell`]s
@@ -108,7 +114,108 @@ pub trait CellOwner: UniqueType {
         c: &'a UtCell<V, Self>,
         d: &'a UtCell<X, Self>,
     ) -> (&'a mut T, &'a mut U, &'a mut V, &'a mut X) {
-        load_all!( self => a, b, c, d )
+        self.get_tuple_mut((a, b, c, d))
+    }
+
+    /// Get five mutable reference to a values in [`UtCell`]s
+    ///
+    /// # Panics
+    ///
+    /// * If any cell isn't owned by self
+    /// * If any cell overlaps with any other cell
+    #[cfg_attr(debug_assertions, track_caller)]
+    #[allow(clippy::type_complexity)]
+    fn get_mut5<'a, T: ?Sized, U: ?Sized, V: ?Sized, X: ?Sized, Y: ?Sized>(
+        &'a mut self,
+        a: &'a UtCell<T, Self>,
+        b: &'a UtCell<U, Self>,
+        c: &'a UtCell<V, Self>,
+        d: &'a UtCell<X, Self>,
+        e: &'a UtCell<Y, Self>,
+    ) -> (&'a mut T, &'a mut U, &'a mut V, &'a mut X, &'a mut Y) {
+        self.get_tuple_mut((a, b, c, d, e))
+    }
+
+    /// Get six mutable reference to a values in [`UtCell`]s
+    ///
+    /// # Panics
+    ///
+    /// * If any cell isn't owned by self
+    /// * If any cell overlaps with any other cell
+    #[cfg_attr(debug_assertions, track_caller)]
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn get_mut6<'a, T: ?Sized, U: ?Sized, V: ?Sized, X: ?Sized, Y: ?Sized, Z: ?Sized>(
+        &'a mut self,
+        a: &'a UtCell<T, Self>,
+        b: &'a UtCell<U, Self>,
+        c: &'a UtCell<V, Self>,
+        d: &'a UtCell<X, Self>,
+        e: &'a UtCell<Y, Self>,
+        f: &'a UtCell<Z, Self>,
+    ) -> (
+        &'a mut T,
+        &'a mut U,
+        &'a mut V,
+        &'a mut X,
+        &'a mut Y,
+        &'a mut Z,
+    ) {
+        self.get_tuple_mut((a, b, c, d, e, f))
+    }
+
+    /// Get a mutable reference to the value in `cells[which]`, along with the cells
+    /// before and after it
+    ///
+    /// The returned mutable reference borrows `self`, so the remaining cells can't be
+    /// loaded through `self` until the mutable reference is dropped. The slices of
+    /// remaining cells only borrow `cells`, so they can be loaded again once
+    /// the mutable reference is no longer used.
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::{CellOwner, UtCell};
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cells = [1, 2, 3].map(|x| UtCell::new(&owner, x));
+    ///
+    ///     let (before, value, after) = owner.split_one_mut(&cells, 1);
+    ///     *value += 10;
+    ///     assert_eq!(before.len(), 1);
+    ///     assert_eq!(after.len(), 1);
+    ///
+    ///     // the mutable borrow of `owner` has ended, so we can read the siblings again
+    ///     assert_eq!(*before[0].load(&owner), 1);
+    ///     assert_eq!(*cells[1].load(&owner), 12);
+    ///     assert_eq!(*after[0].load(&owner), 3);
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If `which` is out of bounds of `cells`
+    /// * If `cells[which]` isn't owned by self
+    ///
+    /// ```should_panic
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::{CellOwner, UtCell};
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cells = [1, 2, 3].map(|x| UtCell::new(&owner, x));
+    ///     owner.split_one_mut(&cells, 4);
+    /// });
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    #[allow(clippy::type_complexity)]
+    fn split_one_mut<'a, 'c: 'a, T>(
+        &'a mut self,
+        cells: &'c [UtCell<T, Self>],
+        which: usize,
+    ) -> (&'c [UtCell<T, Self>], &'a mut T, &'c [UtCell<T, Self>]) {
+        if which >= cells.len() {
+            split_one_mut_out_of_bounds(which, cells.len())
+        }
+
+        let (before, rest) = cells.split_at(which);
+        let (cell, after) = rest.split_at(1);
+        (before, cell[0].load_mut(self), after)
     }
 
     /// Try to get two mutable reference to a values in [`UtCell`]s
@@ -117,7 +224,7 @@ pub trait CellOwner: UniqueType {
         a: &'a UtCell<T, Self>,
         b: &'a UtCell<U, Self>,
     ) -> Result<(&'a mut T, &'a mut U), TryLoadAllError> {
-        load_all!( self => try a, b )
+        self.try_get_tuple_mut((a, b))
     }
 
     /// Try to get three mutable reference to a values in [`UtCell`]s
@@ -127,7 +234,7 @@ pub trait CellOwner: UniqueType {
         b: &'a UtCell<U, Self>,
         c: &'a UtCell<V, Self>,
     ) -> Result<(&'a mut T, &'a mut U, &'a mut V), TryLoadAllError> {
-        load_all!( self => try a, b, c )
+        self.try_get_tuple_mut((a, b, c))
     }
 
     /// Try to get four mutable reference to a values in [`UtCell`]s
@@ -138,10 +245,290 @@ pub trait CellOwner: UniqueType {
         c: &'a UtCell<V, Self>,
         d: &'a UtCell<X, Self>,
     ) -> Result<(&'a mut T, &'a mut U, &'a mut V, &'a mut X), TryLoadAllError> {
-        load_all!( self => try a, b, c, d )
+        self.try_get_tuple_mut((a, b, c, d))
+    }
+
+    /// Try to get five mutable reference to a values in [`UtCell`]s
+    #[allow(clippy::type_complexity)]
+    fn try_get_mut5<'a, T: ?Sized, U: ?Sized, V: ?Sized, X: ?Sized, Y: ?Sized>(
+        &'a mut self,
+        a: &'a UtCell<T, Self>,
+        b: &'a UtCell<U, Self>,
+        c: &'a UtCell<V, Self>,
+        d: &'a UtCell<X, Self>,
+        e: &'a UtCell<Y, Self>,
+    ) -> Result<(&'a mut T, &'a mut U, &'a mut V, &'a mut X, &'a mut Y), TryLoadAllError> {
+        self.try_get_tuple_mut((a, b, c, d, e))
+    }
+
+    /// Try to get six mutable reference to a values in [`UtCell`]s
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn try_get_mut6<'a, T: ?Sized, U: ?Sized, V: ?Sized, X: ?Sized, Y: ?Sized, Z: ?Sized>(
+        &'a mut self,
+        a: &'a UtCell<T, Self>,
+        b: &'a UtCell<U, Self>,
+        c: &'a UtCell<V, Self>,
+        d: &'a UtCell<X, Self>,
+        e: &'a UtCell<Y, Self>,
+        f: &'a UtCell<Z, Self>,
+    ) -> Result<
+        (
+            &'a mut T,
+            &'a mut U,
+            &'a mut V,
+            &'a mut X,
+            &'a mut Y,
+            &'a mut Z,
+        ),
+        TryLoadAllError,
+    > {
+        self.try_get_tuple_mut((a, b, c, d, e, f))
+    }
+
+    /// Get mutable references to the values in a tuple of [`UtCell`]s, which may have
+    /// different types
+    ///
+    /// This works for tuples of up to 12 cells, see [`CellTuple`]
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::{CellOwner, TryLoadAllError, UtCell};
+    /// LifetimeUt::with(|mut owner| {
+    ///     let a = UtCell::new(&owner, 1);
+    ///     let b = UtCell::new(&owner, "b");
+    ///     let c = UtCell::new(&owner, vec![3]);
+    ///
+    ///     let (a_mut, b_mut, c_mut) = owner.get_tuple_mut((&a, &b, &c));
+    ///     *a_mut += 1;
+    ///     *b_mut = "B";
+    ///     c_mut.push(4);
+    ///
+    ///     assert_eq!((*a.load(&owner), *b.load(&owner)), (2, "B"));
+    ///     assert_eq!(*c.load(&owner), [3, 4]);
+    ///
+    ///     let err = owner.try_get_tuple_mut((&a, &b, &c, &b));
+    ///     assert_eq!(err.unwrap_err(), TryLoadAllError::Overlaps { a: 1, b: 3 });
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If any cell isn't owned by self
+    /// * If any cell overlaps with any other cell
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn get_tuple_mut<'a, Tuple: CellTuple<'a, Self>>(&'a mut self, cells: Tuple) -> Tuple::Output {
+        match cells.try_load_mut(self) {
+            Ok(values) => values,
+            Err(err) => get_all_mut_failed(err),
+        }
+    }
+
+    /// Try to get mutable references to the values in a tuple of [`UtCell`]s
+    ///
+    /// see [`CellOwner::get_tuple_mut`]
+    fn try_get_tuple_mut<'a, Tuple: CellTuple<'a, Self>>(
+        &'a mut self,
+        cells: Tuple,
+    ) -> Result<Tuple::Output, TryLoadAllError> {
+        cells.try_load_mut(self)
+    }
+
+    /// Get mutable references to the values in any number of [`UtCell`]s
+    ///
+    /// Unlike [`CellOwner::get_mut4`], the overlap check sorts the cells, so this
+    /// takes O(n log n) time, which makes it practical for large numbers of cells.
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::{CellOwner, UtCell};
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cells = [1, 2, 3, 4, 5].map(|x| UtCell::new(&owner, x));
+    ///
+    ///     let [a, b, c] = owner.get_all_mut([&cells[4], &cells[0], &cells[2]]);
+    ///     core::mem::swap(a, b);
+    ///     *c *= 10;
+    ///
+    ///     assert_eq!(*cells[0].load(&owner), 5);
+    ///     assert_eq!(*cells[2].load(&owner), 30);
+    ///     assert_eq!(*cells[4].load(&owner), 1);
+    ///
+    ///     assert!(owner.try_get_all_mut([&cells[1], &cells[3], &cells[1]]).is_err());
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If any cell isn't owned by self
+    /// * If any cell overlaps with any other cell
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn get_all_mut<'a, T: ?Sized, const N: usize>(
+        &'a mut self,
+        cells: [&'a UtCell<T, Self>; N],
+    ) -> [&'a mut T; N] {
+        match self.try_get_all_mut(cells) {
+            Ok(values) => values,
+            Err(err) => get_all_mut_failed(err),
+        }
+    }
+
+    /// Try to get mutable references to the values in any number of [`UtCell`]s
+    ///
+    /// see [`CellOwner::get_all_mut`]
+    fn try_get_all_mut<'a, T: ?Sized, const N: usize>(
+        &'a mut self,
+        cells: [&'a UtCell<T, Self>; N],
+    ) -> Result<[&'a mut T; N], TryLoadAllError> {
+        for (arg, cell) in cells.iter().enumerate() {
+            if !cell.is_owned_by(self) {
+                return Err(TryLoadAllError::NotOwned { arg });
+            }
+        }
+
+        let mut arg = 0;
+        let mut ranges = cells.map(|cell| {
+            arg += 1;
+            load_all::CellRange::new(cell, arg - 1)
+        });
+        load_all::find_overlap::<Self>(&mut ranges)?;
+
+        let owner: &'a Self = self;
+        // SAFETY: all cells are owned by the owner, and none of them overlap
+        // and we have an exclusive reference to the owner for the entire lifetime 'a
+        Ok(cells.map(|cell| unsafe { cell.load_mut_unchecked(owner) }))
+    }
+
+    /// Get mutable references to the values in a runtime sized list of [`UtCell`]s
+    ///
+    /// This is the slice version of [`CellOwner::get_all_mut`], which already takes an array
+    /// of cells whose length is known at compile time, so it is named `get_all_mut_vec` after
+    /// the [`Vec`](alloc::vec::Vec) it returns. It uses the same sorting overlap check.
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::{CellOwner, TryLoadAllError, UtCell};
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cells = [1, 2, 3, 4, 5].map(|x| UtCell::new(&owner, x));
+    ///     let selected = cells.iter().step_by(2).collect::<Vec<_>>();
+    ///
+    ///     for value in owner.get_all_mut_vec(&selected) {
+    ///         *value *= 10;
+    ///     }
+    ///
+    ///     assert_eq!(cells.each_ref().map(|cell| *cell.load(&owner)), [10, 2, 30, 4, 50]);
+    ///
+    ///     let err = owner.try_get_all_mut_vec(&[&cells[1], &cells[3], &cells[1]]);
+    ///     assert_eq!(err.unwrap_err(), TryLoadAllError::Overlaps { a: 0, b: 2 });
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If any cell isn't owned by self
+    /// * If any cell overlaps with any other cell
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn get_all_mut_vec<'a, T: ?Sized>(
+        &'a mut self,
+        cells: &[&'a UtCell<T, Self>],
+    ) -> alloc::vec::Vec<&'a mut T> {
+        match self.try_get_all_mut_vec(cells) {
+            Ok(values) => values,
+            Err(err) => get_all_mut_failed(err),
+        }
+    }
+
+    /// Try to get mutable references to the values in a runtime sized list of [`UtCell`]s
+    ///
+    /// see [`CellOwner::get_all_mut_vec`]
+    #[cfg(feature = "alloc")]
+    fn try_get_all_mut_vec<'a, T: ?Sized>(
+        &'a mut self,
+        cells: &[&'a UtCell<T, Self>],
+    ) -> Result<alloc::vec::Vec<&'a mut T>, TryLoadAllError> {
+        for (arg, cell) in cells.iter().enumerate() {
+            if !cell.is_owned_by(self) {
+                return Err(TryLoadAllError::NotOwned { arg });
+            }
+        }
+
+        let mut ranges = cells
+            .iter()
+            .enumerate()
+            .map(|(arg, cell)| load_all::CellRange::new(cell, arg))
+            .collect::<alloc::vec::Vec<_>>();
+        load_all::find_overlap::<Self>(&mut ranges)?;
+
+        let owner: &'a Self = self;
+        // SAFETY: all cells are owned by the owner, and none of them overlap
+        // and we have an exclusive reference to the owner for the entire lifetime 'a
+        Ok(cells
+            .iter()
+            .map(|cell| unsafe { cell.load_mut_unchecked(owner) })
+            .collect())
     }
 }
 
+/// A tuple of references to [`UtCell`]s which can be loaded together, see
+/// [`CellOwner::get_tuple_mut`]
+///
+/// This is implemented for tuples of 1 to 12 cells, which all have the same owner
+pub trait CellTuple<'a, C: CellOwner + ?Sized>: load_all::Seal {
+    /// The tuple of mutable references to the values in the cells
+    type Output;
+
+    /// Try to load all the cells, see [`CellOwner::try_get_tuple_mut`]
+    fn try_load_mut(self, owner: &'a mut C) -> Result<Self::Output, TryLoadAllError>;
+}
+
+macro_rules! cell_tuple {
+    ($($value:ident: $ty:ident),+) => {
+        impl<'a, C: CellOwner + ?Sized, $($ty: ?Sized),+> load_all::Seal
+            for ($(&'a UtCell<$ty, C>,)+)
+        {
+        }
+
+        impl<'a, C: CellOwner + ?Sized, $($ty: ?Sized),+> CellTuple<'a, C>
+            for ($(&'a UtCell<$ty, C>,)+)
+        {
+            type Output = ($(&'a mut $ty,)+);
+
+            // load_all doesn't return a tuple for a single cell
+            #[allow(unused_parens)]
+            fn try_load_mut(self, owner: &'a mut C) -> Result<Self::Output, TryLoadAllError> {
+                let ($($value,)+) = self;
+                load_all!(owner => try $($value),+).map(|($($value),+)| ($($value,)+))
+            }
+        }
+    };
+}
+
+cell_tuple!(a: A);
+cell_tuple!(a: A, b: B);
+cell_tuple!(a: A, b: B, c: C2);
+cell_tuple!(a: A, b: B, c: C2, d: D);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G, h: H);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G, h: H, i: I);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G, h: H, i: I, j: J);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G, h: H, i: I, j: J, k: K);
+cell_tuple!(a: A, b: B, c: C2, d: D, e: E, f: F, g: G, h: H, i: I, j: J, k: K, l: L);
+
+#[cold]
+#[inline(never)]
+#[cfg_attr(debug_assertions, track_caller)]
+fn get_all_mut_failed(err: TryLoadAllError) -> ! {
+    panic!("Failed to load all cells: {err:?}")
+}
+
+#[cold]
+#[inline(never)]
+#[cfg_attr(debug_assertions, track_caller)]
+fn split_one_mut_out_of_bounds(which: usize, len: usize) -> ! {
+    panic!("Index out of bounds (index >= length), index: {which}, length: {len}")
+}
+
 /// A [`UtCell`] allows accessing references to the interior value
 /// when you have a witness unique type that "owns" this [`UtCell`]
 #[repr(C)]
@@ -161,6 +548,54 @@ where
 {
 }
 
+/// Doesn't print the value, since that requires the owner, see [`UtCell::debug_with`]
+impl<T: ?Sized, C: CellOwner + ?Sized> core::fmt::Debug for UtCell<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UtCell").finish_non_exhaustive()
+    }
+}
+
+/// Only available for trivial tokens, since otherwise a token must come from the owner
+///
+/// ```
+/// # use unique_types::lifetime::LifetimeUt;
+/// # use ut_cell::UtCell;
+/// #[derive(Default)]
+/// struct Counters<'a> {
+///     hits: UtCell<u32, LifetimeUt<'a>>,
+///     misses: UtCell<u32, LifetimeUt<'a>>,
+/// }
+///
+/// LifetimeUt::with(|mut owner| {
+///     let counters = Counters::default();
+///     *counters.hits.load_mut(&mut owner) += 1;
+///     assert_eq!(*counters.hits.load(&owner), 1);
+///     assert_eq!(*counters.misses.load(&owner), 0);
+/// });
+/// ```
+impl<T: Default, C: CellOwner + ?Sized> Default for UtCell<T, C>
+where
+    C::Token: TrivialToken,
+{
+    fn default() -> Self {
+        Self::new_trivial(T::default())
+    }
+}
+
+/// Formats the value in a [`UtCell`], created from [`UtCell::debug_with`]
+pub struct DebugWith<'a, T: ?Sized, C: CellOwner + ?Sized> {
+    cell: &'a UtCell<T, C>,
+    owner: &'a C,
+}
+
+impl<T: ?Sized + core::fmt::Debug, C: CellOwner + ?Sized> core::fmt::Debug for DebugWith<'_, T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UtCell")
+            .field("value", &self.cell.load(self.owner))
+            .finish()
+    }
+}
+
 fn validate_trivial_token<T: TrivialToken>(get_align: impl FnOnce() -> usize) {
     fn illegal_trivial_token<T>() -> ! {
         panic!(
@@ -194,6 +629,101 @@ where
         // aligned, zero sized, and trivial to construct
         unsafe { &mut *(x as *mut T as *mut Self) }
     }
+
+    /// Convert a [`UtCell`] to a [`UtCell`] of part of it's value
+    ///
+    /// This can only be done when the Token of the [`CellOwner`] is a
+    /// 1 aligned ZST. see [`project!`] for a safe way to project to fields.
+    ///
+    /// # Safety
+    ///
+    /// `f` must return a pointer to a field of the value behind the pointer it's given,
+    /// without reading or writing the value. The field must be aligned, so it can't be a field
+    /// of a `#[repr(packed)]` struct
+    #[inline]
+    pub unsafe fn project_unchecked<U>(&self, f: impl FnOnce(*mut T) -> *mut U) -> &UtCell<U, C> {
+        validate_trivial_token::<C::Token>(mem::align_of::<U>);
+
+        let field = f(self.as_ptr());
+
+        // SAFETY: validate_trivial_token ensures that the token type is sufficiently
+        // aligned, zero sized, and trivial to construct. The caller ensures that field points
+        // into the value, so it is inside the UnsafeCell and lives as long as self
+        unsafe { &*(field as *const UtCell<U, C>) }
+    }
+}
+
+/// Project a `&UtCell<Struct, C>` to a `&UtCell<Field, C>`
+///
+/// `project!(cell, Struct, field)` returns a cell of `cell`'s `field`, which must be a field
+/// declared on `Struct` (not one reached through [`Deref`](core::ops::Deref)). The token of `C`
+/// must be a [`TrivialToken`](unique_types::TrivialToken), see [`UtCell::project_unchecked`].
+///
+/// Projected cells of different fields don't overlap, so they can be loaded together.
+///
+/// ```
+/// # use unique_types::lifetime::LifetimeUt;
+/// # use ut_cell::{CellOwner, UtCell};
+/// struct Foo {
+///     a: u32,
+///     b: String,
+/// }
+///
+/// LifetimeUt::with(|mut owner| {
+///     let foo = UtCell::new(&owner, Foo { a: 1, b: String::from("hello") });
+///
+///     let a = ut_cell::project!(&foo, Foo, a);
+///     let b = ut_cell::project!(&foo, Foo, b);
+///
+///     let (a, b) = owner.get_mut2(a, b);
+///     *a += 1;
+///     b.push_str(" world");
+///
+///     let foo = foo.load(&owner);
+///     assert_eq!(foo.a, 2);
+///     assert_eq!(foo.b, "hello world");
+/// });
+/// ```
+///
+/// Fields of packed structs may be misaligned, so they can't be projected
+///
+/// ```compile_fail,E0793
+/// # use unique_types::lifetime::LifetimeUt;
+/// # use ut_cell::UtCell;
+/// #[repr(C, packed)]
+/// struct Packed {
+///     a: u8,
+///     b: u32,
+/// }
+///
+/// LifetimeUt::with(|owner| {
+///     let packed = UtCell::new(&owner, Packed { a: 1, b: 2 });
+///     let b = ut_cell::project!(&packed, Packed, b);
+/// });
+/// ```
+#[macro_export]
+macro_rules! project {
+    ($cell:expr, $ty:path, $field:tt) => {
+        match $cell {
+            cell => {
+                let cell: &$crate::UtCell<$ty, _> = cell;
+
+                // ensure that the field is declared on the type, and isn't behind a Deref.
+                // binding by reference also rejects fields of packed structs, which may be
+                // misaligned
+                let _ = |value: &$ty| {
+                    let $ty {
+                        $field: ref _field, ..
+                    } = *value;
+                };
+
+                // SAFETY: the pointer points to a field declared on the value
+                unsafe {
+                    cell.project_unchecked(|ptr: *mut $ty| ::core::ptr::addr_of_mut!((*ptr).$field))
+                }
+            }
+        }
+    };
 }
 
 impl<T, C: CellOwner + ?Sized> UtCell<[T], C>
@@ -241,6 +771,76 @@ where
     }
 }
 
+impl<T, C: CellOwner + ?Sized> UtCell<Option<T>, C> {
+    /// Insert the value computed from `f` if this cell is [`None`], then return a mutable
+    /// reference to the value
+    ///
+    /// see [`Option::get_or_insert_with`]
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::UtCell;
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cell = UtCell::new(&owner, None);
+    ///     let mut calls = 0;
+    ///
+    ///     for _ in 0..2 {
+    ///         *cell.get_or_insert_with(&mut owner, || {
+    ///             calls += 1;
+    ///             0
+    ///         }) += 1;
+    ///     }
+    ///
+    ///     assert_eq!(calls, 1);
+    ///     assert_eq!(*cell.load(&owner), Some(2));
+    /// });
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// If this type isn't owned by the owner, then this function panics
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn get_or_insert_with<'a>(&'a self, owner: &'a mut C, f: impl FnOnce() -> T) -> &'a mut T {
+        self.load_mut(owner).get_or_insert_with(f)
+    }
+}
+
+impl<T, C: CellOwner + ?Sized> UtCell<[T], C> {
+    /// Iterate over references to the elements in this cell
+    ///
+    /// # Panic
+    ///
+    /// If this type isn't owned by the owner, then this function panics
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn iter<'a>(&'a self, owner: &'a C) -> core::slice::Iter<'a, T> {
+        self.load(owner).iter()
+    }
+
+    /// Iterate over mutable references to the elements in this cell
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::UtCell;
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cell: &UtCell<[i32], _> = &UtCell::new(&owner, [1, 2, 3]);
+    ///
+    ///     for value in cell.iter_mut(&mut owner) {
+    ///         *value *= 2;
+    ///     }
+    ///
+    ///     assert_eq!(cell.iter(&owner).sum::<i32>(), 12);
+    /// });
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// If this type isn't owned by the owner, then this function panics
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn iter_mut<'a>(&'a self, owner: &'a mut C) -> core::slice::IterMut<'a, T> {
+        self.load_mut(owner).iter_mut()
+    }
+}
+
 impl<T, C: CellOwner + ?Sized, const N: usize> UtCell<[T; N], C>
 where
     C::Token: TrivialToken,
@@ -301,6 +901,15 @@ impl<T: ?Sized, C: CellOwner + ?Sized> UtCell<T, C> {
     pub fn as_ptr(&self) -> *mut T {
         self.value.get()
     }
+
+    /// Get a reference to the [`UnsafeCell`] that holds the underlying value
+    ///
+    /// This is for interop with APIs that work with [`UnsafeCell`]s directly, creating
+    /// references to the value from it has the same requirements as [`UtCell::as_ptr`].
+    #[inline]
+    pub fn as_unsafe_cell(&self) -> &UnsafeCell<T> {
+        &self.value
+    }
 }
 
 impl<T, C: CellOwner + ?Sized> UtCell<T, C> {
@@ -316,6 +925,92 @@ impl<T, C: CellOwner + ?Sized> UtCell<T, C> {
             value: UnsafeCell::new(value),
         }
     }
+
+    /// Get the value out of the [`UtCell`]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    /// Construct a [`UtCell`] without a [`CellOwner`], when it's token is trivial
+    ///
+    /// Loading the value still requires the owner, since the owner is what
+    /// guarantees that shared and mutable loads don't overlap.
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::UtCell;
+    /// LifetimeUt::with(|mut owner| {
+    ///     let cell = UtCell::<_, LifetimeUt>::new_trivial(10);
+    ///     *cell.load_mut(&mut owner) += 1;
+    ///     assert_eq!(*cell.load(&owner), 11);
+    /// });
+    /// ```
+    pub const fn new_trivial(value: T) -> Self
+    where
+        C::Token: TrivialToken,
+    {
+        Self::from_token(C::Token::NEW, value)
+    }
+
+    /// Replace the value in this cell, and return the old value
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::UtCell;
+    /// LifetimeUt::with(|mut owner| {
+    ///     let a = UtCell::new(&owner, vec![1]);
+    ///     let b = UtCell::new(&owner, vec![2, 3]);
+    ///
+    ///     assert_eq!(a.replace(&mut owner, vec![4]), [1]);
+    ///     a.swap(&b, &mut owner);
+    ///     a.swap(&a, &mut owner);
+    ///     assert_eq!(b.take(&mut owner), [4]);
+    ///
+    ///     assert_eq!(*a.load(&owner), [2, 3]);
+    ///     assert!(b.load(&owner).is_empty());
+    /// });
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// If this type isn't owned by the owner, then this function panics
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn replace(&self, owner: &mut C, value: T) -> T {
+        mem::replace(self.load_mut(owner), value)
+    }
+
+    /// Take the value in this cell, leaving [`Default::default`] in it's place
+    ///
+    /// # Panic
+    ///
+    /// If this type isn't owned by the owner, then this function panics
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn take(&self, owner: &mut C) -> T
+    where
+        T: Default,
+    {
+        self.replace(owner, T::default())
+    }
+
+    /// Swap the values in two cells
+    ///
+    /// If both are the same cell, then this does nothing
+    ///
+    /// # Panic
+    ///
+    /// * If either cell isn't owned by the owner
+    /// * If the cells partially overlap
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn swap(&self, other: &Self, owner: &mut C) {
+        self.assert_owned_by(owner);
+
+        if core::ptr::eq(self, other) {
+            return;
+        }
+
+        let (a, b) = owner.get_mut2(self, other);
+        mem::swap(a, b)
+    }
 }
 
 impl<T: ?Sized, C: CellOwner + ?Sized> UtCell<T, C> {
@@ -382,6 +1077,26 @@ impl<T: ?Sized, C: CellOwner + ?Sized> UtCell<T, C> {
         unsafe { &mut *self.as_ptr() }
     }
 
+    /// Format the value in this cell by loading it through the owner
+    ///
+    /// ```
+    /// # use unique_types::lifetime::LifetimeUt;
+    /// # use ut_cell::UtCell;
+    /// LifetimeUt::with(|owner| {
+    ///     let cell = UtCell::new(&owner, 10);
+    ///
+    ///     assert_eq!(format!("{cell:?}"), "UtCell { .. }");
+    ///     assert_eq!(format!("{:?}", cell.debug_with(&owner)), "UtCell { value: 10 }");
+    /// });
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Formatting panics if this type isn't owned by the owner
+    pub fn debug_with<'a>(&'a self, owner: &'a C) -> DebugWith<'a, T, C> {
+        DebugWith { cell: self, owner }
+    }
+
     #[doc(hidden)]
     #[allow(clippy::mut_from_ref)]
     pub unsafe fn load_mut_unchecked<'a>(&'a self, _owner: &'a C) -> &'a mut T {
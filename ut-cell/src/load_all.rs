@@ -47,6 +47,60 @@ macro_rules! load_all {
     }};
 }
 
+/// Get mutable references to the values in any number of [`UtCell`]s, which may have
+/// different types
+///
+/// `get_all!(owner => a, b, c)` returns a tuple of mutable references, and
+/// `get_all!(owner => try a, b, c)` returns a [`Result`] with a
+/// [`TryLoadAllError`](crate::TryLoadAllError) instead of panicking. The indices in the error
+/// are the positions of the arguments, starting from 0.
+///
+/// The overlap check compares every pair of cells, so this is O(n^2) in the number of cells,
+/// see [`CellOwner::get_all_mut`] for large numbers of cells of the same type.
+///
+/// ```
+/// # use unique_types::lifetime::LifetimeUt;
+/// # use ut_cell::{TryLoadAllError, UtCell};
+/// LifetimeUt::with(|mut owner| {
+///     let cells = [1, 2, 3, 4, 5, 6].map(|x| UtCell::new(&owner, x));
+///     let name = UtCell::new(&owner, "seven");
+///
+///     let (a, b, c, d, e, f, g) = ut_cell::get_all!(
+///         &mut owner => &cells[0], &cells[1], &cells[2], &cells[3], &cells[4], &cells[5], &name
+///     );
+///     *a += 10;
+///     *f += 60;
+///     *g = "SEVEN";
+///     assert_eq!((*b, *c, *d, *e), (2, 3, 4, 5));
+///
+///     assert_eq!(*cells[0].load(&owner), 11);
+///     assert_eq!(*cells[5].load(&owner), 66);
+///     assert_eq!(*name.load(&owner), "SEVEN");
+///
+///     let err = ut_cell::get_all!(&mut owner => try &cells[0], &cells[1], &cells[2], &cells[1]);
+///     assert_eq!(err.unwrap_err(), TryLoadAllError::Overlaps { a: 1, b: 3 });
+/// });
+/// ```
+#[macro_export]
+macro_rules! get_all {
+    (@bind $owner:expr; [$($cell:ident)*];) => {
+        $crate::load_all![$owner => try $($cell),*]
+    };
+    (@bind $owner:expr; [$($cell:ident)*]; $value:expr $(, $rest:expr)*) => {
+        match $value {
+            // each expansion introduces a new `cell`, which is distinct from all the
+            // others due to macro hygiene
+            ref cell => $crate::get_all!(@bind $owner; [$($cell)* cell]; $($rest),*),
+        }
+    };
+    ($owner:expr => try $($value:expr),+ $(,)?) => {
+        $crate::get_all!(@bind $owner; []; $($value),+)
+    };
+    ($owner:expr => $($value:expr),+ $(,)?) => {
+        $crate::get_all!(@bind $owner; []; $($value),+).unwrap()
+    };
+}
+
 pub trait Seal {}
 
 /// # Safety
@@ -85,7 +139,7 @@ impl<T, Ts: Seal> Seal for Cons<T, Ts> {}
 // assert_owned_by does check that all values in the list are owned by the given owner
 // there is only one element in the list, so there can't be any overlaps
 // overlaps_with does check that all cells in the list don't overlap with the given memory region
-unsafe impl<'a, T: ?Sized, C: CellOwner + ?Sized> CellList for Cons<&'a UtCell<T, C>, Nil> {
+unsafe impl<T: ?Sized, C: CellOwner + ?Sized> CellList for Cons<&UtCell<T, C>, Nil> {
     type Owner = C;
 
     fn is_owned_by(&self, owner: &Self::Owner, i: usize) -> Result<(), super::TryLoadAllError> {
@@ -119,7 +173,7 @@ unsafe impl<'a, T: ?Sized, C: CellOwner + ?Sized> CellList for Cons<&'a UtCell<T
 // assert_owned_by does check that all values in the list are owned by the given owner
 // the head is checked that it doesn't overlap with any other element in the list
 // overlaps_with does check that all cells in the list don't overlap with the given memory region
-unsafe impl<'a, T: ?Sized, Ts: CellList> CellList for Cons<&'a UtCell<T, Ts::Owner>, Ts> {
+unsafe impl<T: ?Sized, Ts: CellList> CellList for Cons<&UtCell<T, Ts::Owner>, Ts> {
     type Owner = Ts::Owner;
 
     fn is_owned_by(&self, owner: &Self::Owner, i: usize) -> Result<(), crate::TryLoadAllError> {
@@ -194,3 +248,78 @@ impl<T: ?Sized, O: CellOwner + ?Sized, Ts> Cons<&UtCell<T, O>, Ts> {
         )
     }
 }
+
+/// The memory region of a cell, and it's position in the argument list
+#[derive(Clone, Copy)]
+pub(crate) struct CellRange {
+    pub start: usize,
+    pub size: usize,
+    pub arg: usize,
+}
+
+impl CellRange {
+    pub fn new<T: ?Sized, C: CellOwner + ?Sized>(cell: &UtCell<T, C>, arg: usize) -> Self {
+        // ZSTs don't overlap, so treat them as empty regions
+        let size = if core::mem::size_of_val(&cell.value) == 0 {
+            0
+        } else {
+            core::mem::size_of_val(cell)
+        };
+
+        Self {
+            start: cell as *const UtCell<T, C> as *const u8 as usize,
+            size,
+            arg,
+        }
+    }
+}
+
+/// Find any pair of cells that overlap in O(n log n) time
+///
+/// This is the generalization of `Cons::head_overlaps_with` to a whole list of cells
+pub(crate) fn find_overlap<O: CellOwner + ?Sized>(
+    ranges: &mut [CellRange],
+) -> Result<(), super::TryLoadAllError> {
+    ranges.sort_unstable_by_key(|range| range.start);
+
+    let overlaps = |a: &CellRange, b: &CellRange| super::TryLoadAllError::Overlaps {
+        a: a.arg.min(b.arg),
+        b: a.arg.max(b.arg),
+    };
+
+    let mut ranges = ranges.iter().filter(|range| range.size != 0);
+
+    if core::mem::size_of::<O::Token>() == 0 {
+        // if the token is a ZST, then it's possible for the cells to overlap
+        // so we need to check if any cell starts before the end of any previous cell
+        let Some(mut furthest) = ranges.next() else {
+            return Ok(());
+        };
+
+        for range in ranges {
+            if range.start < furthest.start + furthest.size {
+                return Err(overlaps(furthest, range));
+            }
+
+            if range.start + range.size > furthest.start + furthest.size {
+                furthest = range;
+            }
+        }
+    } else {
+        // if the token is not a ZST, then it is impossible for cells to overlap
+        // in this case just check for duplicate pointers, which will be adjacent
+        let Some(mut prev) = ranges.next() else {
+            return Ok(());
+        };
+
+        for range in ranges {
+            if range.start == prev.start {
+                return Err(overlaps(prev, range));
+            }
+
+            prev = range;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+//! A thread-safe interner which hands out small [`Copy`] handles
+//!
+//! see [`Interner`] for details
+
+use core::{
+    hash::Hash,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use alloc::{boxed::Box, vec::Vec};
+use std::{collections::HashMap, sync::Mutex};
+
+const BUCKET_COUNT: usize = usize::BITS as usize;
+
+/// A handle to a value in an [`Interner`], created from [`Interner::intern`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle {
+    index: usize,
+}
+
+impl Handle {
+    /// Get the index of the value in the [`Interner`]
+    ///
+    /// Values are indexed in the order they were first interned
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.index
+    }
+}
+
+/// A thread-safe interner, which deduplicates values and hands out [`Handle`]s to them
+///
+/// Like [`UtVec`](crate::UtVec), this is append-only. Values are never removed or moved
+/// once they are interned, which is what makes it sound for [`Interner::resolve`] to return
+/// a `&T` without holding any locks. Values are stored in buckets that double in size,
+/// and buckets are never reallocated.
+///
+/// Interning takes a lock, and clones the value once so it can be used to deduplicate
+/// later values.
+///
+/// ```
+/// use ut_vec::interner::Interner;
+///
+/// let interner = Interner::new();
+///
+/// let (a, b) = std::thread::scope(|s| {
+///     let a = s.spawn(|| interner.intern("hello"));
+///     let b = s.spawn(|| interner.intern("hello"));
+///     (a.join().unwrap(), b.join().unwrap())
+/// });
+///
+/// assert_eq!(a, b);
+/// assert_eq!(*interner.resolve(a), "hello");
+///
+/// let c = interner.intern("world");
+/// assert_ne!(a, c);
+/// assert_eq!(*interner.resolve(c), "world");
+/// assert_eq!(interner.len(), 2);
+/// ```
+pub struct Interner<T> {
+    map: Mutex<HashMap<T, usize>>,
+    len: AtomicUsize,
+    buckets: [AtomicPtr<T>; BUCKET_COUNT],
+}
+
+// SAFETY: the interner owns values of type T, so it can be sent to another thread
+// if T can be sent to another thread
+unsafe impl<T: Send> Send for Interner<T> {}
+// SAFETY: the interner allows inserting values from any thread (requires T: Send)
+// and getting shared references to values from any thread (requires T: Sync)
+unsafe impl<T: Send + Sync> Sync for Interner<T> {}
+
+/// Get the bucket and the offset into the bucket for an index
+///
+/// Bucket `b` holds `2^b` values, and starts at index `2^b - 1`
+#[inline]
+fn location(index: usize) -> (usize, usize) {
+    let n = index + 1;
+    let bucket = (usize::BITS - 1 - n.leading_zeros()) as usize;
+    (bucket, n - (1 << bucket))
+}
+
+impl<T> Interner<T> {
+    /// Create a new empty [`Interner`]
+    pub fn new() -> Self {
+        Self {
+            map: Mutex::new(HashMap::new()),
+            len: AtomicUsize::new(0),
+            buckets: [const { AtomicPtr::new(core::ptr::null_mut()) }; BUCKET_COUNT],
+        }
+    }
+
+    /// The number of distinct values in the [`Interner`]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Returns true if no values have been interned
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the value associated with the handle
+    ///
+    /// # Panics
+    ///
+    /// If the handle wasn't created by this [`Interner`], and is out of bounds
+    #[inline]
+    pub fn resolve(&self, handle: Handle) -> &T {
+        let len = self.len();
+        if handle.index >= len {
+            handle_out_of_bounds(handle.index, len)
+        }
+
+        let (bucket, offset) = location(handle.index);
+        let bucket = self.buckets[bucket].load(Ordering::Acquire);
+        // SAFETY: all values before len are initialized, and are never moved or removed
+        // the Acquire load of len synchronizes with the Release store in `intern`, so
+        // the bucket and the value are visible to this thread
+        unsafe { &*bucket.add(offset) }
+    }
+
+    /// Write the value at the given index
+    ///
+    /// # Safety
+    ///
+    /// Must only be called while holding the lock on `self.map`, with `index == self.len`
+    unsafe fn write(&self, index: usize, value: T) {
+        let (bucket_index, offset) = location(index);
+        let bucket = &self.buckets[bucket_index];
+        let mut ptr = bucket.load(Ordering::Relaxed);
+
+        if ptr.is_null() {
+            let bucket_len = 1 << bucket_index;
+            let mut values = Vec::<MaybeUninit<T>>::with_capacity(bucket_len);
+            // SAFETY: MaybeUninit doesn't require initialization, and the capacity
+            // is at least bucket_len
+            unsafe { values.set_len(bucket_len) }
+            ptr = Box::into_raw(values.into_boxed_slice()).cast::<T>();
+            bucket.store(ptr, Ordering::Release);
+        }
+
+        // SAFETY: offset is in bounds of the bucket, and the caller ensures that
+        // no one else is writing to this interner, and no one can read this index
+        // until len is updated
+        unsafe { ptr.add(offset).write(value) }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    /// Get the handle of a value, inserting it if it's not already in the [`Interner`]
+    pub fn intern(&self, value: T) -> Handle {
+        let mut map = self
+            .map
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if let Some(&index) = map.get(&value) {
+            return Handle { index };
+        }
+
+        let index = self.len.load(Ordering::Relaxed);
+        // SAFETY: we are holding the lock, and index is the current length
+        unsafe { self.write(index, value.clone()) }
+        self.len.store(index + 1, Ordering::Release);
+        map.insert(value, index);
+
+        Handle { index }
+    }
+
+    /// Get the handle of a value, if it's already in the [`Interner`]
+    pub fn get(&self, value: &T) -> Option<Handle> {
+        let map = self
+            .map
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        map.get(value).map(|&index| Handle { index })
+    }
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Interner<T> {
+    fn drop(&mut self) {
+        let len = *self.len.get_mut();
+
+        for index in 0..len {
+            let (bucket, offset) = location(index);
+            let bucket = *self.buckets[bucket].get_mut();
+            // SAFETY: all values before len are initialized, and are only dropped once
+            unsafe { bucket.add(offset).drop_in_place() }
+        }
+
+        for (bucket, ptr) in self.buckets.iter_mut().enumerate() {
+            let ptr = *ptr.get_mut();
+            if ptr.is_null() {
+                continue;
+            }
+
+            let slice =
+                core::ptr::slice_from_raw_parts_mut(ptr.cast::<MaybeUninit<T>>(), 1 << bucket);
+            // SAFETY: all non-null buckets were created from a boxed slice of this length
+            drop(unsafe { Box::from_raw(slice) })
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn handle_out_of_bounds(index: usize, len: usize) -> ! {
+    panic!("Tried to resolve a handle at index {index}, but the interner only has {len} values")
+}
@@ -14,6 +14,11 @@
 //! [`UtVec`] is a append-only vector when used with a [`UniqueToken`]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+pub mod interner;
 
 use core::{
     ops::{self, RangeBounds},
@@ -48,6 +53,68 @@ impl<O: ?Sized + UniqueToken> Clone for UtIndex<O> {
     }
 }
 
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> core::fmt::Display for UtIndex<O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.index.fmt(f)
+    }
+}
+
+/// Equality only compares the underlying index, and ignores the token
+///
+/// The token only witnesses which owner the index belongs to, and indices from different kinds
+/// of owners already have different types. Indices from two owners of the same type which have
+/// the same position compare equal.
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> PartialEq for UtIndex<O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> Eq for UtIndex<O> {}
+
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> PartialOrd for UtIndex<O> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders by the underlying index, see [`PartialEq`] for details
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> Ord for UtIndex<O> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+/// Hashes the underlying index, see [`PartialEq`] for details
+///
+/// ```
+/// use std::collections::HashMap;
+/// use unique_types::runtime::RuntimeUt;
+/// use ut_vec::UtVec;
+///
+/// let mut vec = UtVec::from_owner(RuntimeUt::new());
+/// let a = vec.push_indexed('a');
+/// let b = vec.push_indexed('b');
+///
+/// let mut names = HashMap::new();
+/// names.insert(a, "first");
+/// names.insert(b, "second");
+///
+/// assert_eq!(names[&b], "second");
+/// assert!(a < b);
+/// ```
+#[cfg(feature = "unique-types")]
+impl<O: ?Sized + UniqueToken> core::hash::Hash for UtIndex<O> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
 #[cfg(feature = "unique-types")]
 impl<O: ?Sized + UniqueToken> UtIndex<O> {
     /// Get the underlying index
@@ -56,6 +123,32 @@ impl<O: ?Sized + UniqueToken> UtIndex<O> {
         self.index
     }
 
+    /// Create a [`UtIndex`] if the index is in bounds of the vector
+    ///
+    /// This is the safe counterpart to [`UtIndex::new_unchecked`], after checking an index once
+    /// it can be used to index the vector without any more bounds checks.
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::{UtIndex, UtVec};
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.extend(['a', 'b', 'c']);
+    ///
+    /// let index = UtIndex::try_new(2, &vec).unwrap();
+    /// assert_eq!(vec[index], 'c');
+    /// assert!(UtIndex::try_new(3, &vec).is_none());
+    /// ```
+    #[inline]
+    pub fn try_new<T>(index: usize, vec: &UtVec<T, O>) -> Option<Self> {
+        if index < vec.len() {
+            // SAFETY: we just checked that the index is in bounds
+            Some(unsafe { Self::new_unchecked(index, vec.owner()) })
+        } else {
+            None
+        }
+    }
+
     /// # Safety
     ///
     /// The index must be in bounds of the [`UtVec`] that is owns the owner
@@ -66,6 +159,23 @@ impl<O: ?Sized + UniqueToken> UtIndex<O> {
             token: owner.token(),
         }
     }
+
+    /// Create a [`UtIndex`] from a token which was taken from an owner earlier
+    ///
+    /// # Safety
+    ///
+    /// The index must be in bounds of the [`UtVec`] that is owned by the owner
+    /// that created the token
+    #[inline]
+    pub const unsafe fn from_token_unchecked(index: usize, token: O::Token) -> Self {
+        Self { index, token }
+    }
+
+    /// Get the token of the owner this index belongs to
+    #[inline]
+    pub const fn token(&self) -> O::Token {
+        self.token
+    }
 }
 
 impl<T> UtVec<T> {
@@ -81,12 +191,163 @@ impl<T> UtVec<T> {
     }
 }
 
+impl<T> UtVec<T> {
+    /// Split the vector into two at the given index, returning the suffix
+    ///
+    /// This is safe because the [`UtVec<T>`] can only be indexed with checked indices
+    ///
+    /// see [`Vec::split_off`]
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::from_vec(vec![1, 2, 3, 4]);
+    /// let tail = vec.split_off(1);
+    /// assert_eq!(vec.as_slice(), [1]);
+    /// assert_eq!(tail, [2, 3, 4]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Vec<T> {
+        self.data.split_off(at)
+    }
+}
+
+/// Clones the elements and the owner
+///
+/// Owners which are [`UniqueToken`]s can't be cloned, since that would create a second owner
+/// which accepts the same indices. So this is mostly useful for the `()` owner.
+///
+/// ```
+/// let vec = ut_vec::UtVec::from_vec(vec![1u8, 2, 3]);
+/// let mut copy = vec.clone();
+/// copy[0] = 10;
+///
+/// assert_eq!(vec.as_slice(), [1, 2, 3]);
+/// assert_eq!(copy.as_slice(), [10, 2, 3]);
+/// ```
+impl<T: Clone, O: Clone> Clone for UtVec<T, O> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            owner: self.owner.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.owner.clone_from(&source.owner);
+    }
+}
+
+/// Compares the elements, ignoring the owners
+///
+/// ```
+/// use unique_types::runtime::RuntimeUt;
+/// use ut_vec::UtVec;
+///
+/// let mut a = UtVec::from_owner(RuntimeUt::new());
+/// let mut b = UtVec::from_owner(RuntimeUt::new());
+/// a.extend([1, 2, 3]);
+/// b.extend([1, 2, 3]);
+///
+/// assert!(a == b);
+/// assert!(a == vec![1, 2, 3]);
+/// assert!(a == *[1, 2, 3].as_slice());
+/// assert!(a != UtVec::from_vec(vec![1, 2]));
+/// ```
+impl<T: PartialEq<U>, U, O: ?Sized, O2: ?Sized> PartialEq<UtVec<U, O2>> for UtVec<T, O> {
+    #[inline]
+    fn eq(&self, other: &UtVec<U, O2>) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T: Eq, O: ?Sized> Eq for UtVec<T, O> {}
+
+impl<T: PartialEq<U>, U, O: ?Sized> PartialEq<[U]> for UtVec<T, O> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.data == other
+    }
+}
+
+impl<T: PartialEq<U>, U, O: ?Sized> PartialEq<Vec<U>> for UtVec<T, O> {
+    #[inline]
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.data == *other
+    }
+}
+
 impl<T> Default for UtVec<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Only vectors without an owner can be collected into, since other owners must be
+/// provided explicitly
+///
+/// ```
+/// let vec: ut_vec::UtVec<u32> = (0..4).collect();
+/// assert_eq!(vec.as_slice(), [0, 1, 2, 3]);
+/// ```
+impl<T> FromIterator<T> for UtVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T> From<Vec<T>> for UtVec<T> {
+    #[inline]
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data)
+    }
+}
+
+impl<T, O> From<UtVec<T, O>> for Vec<T> {
+    #[inline]
+    fn from(vec: UtVec<T, O>) -> Self {
+        vec.into_vec()
+    }
+}
+
+/// A [`UtVec`] branded with a unique lifetime, see [`UtVec::with_brand`]
+#[cfg(feature = "unique-types")]
+pub type BrandedVec<'id, T> = UtVec<T, unique_types::lifetime::LifetimeUt<'id>>;
+
+#[cfg(feature = "unique-types")]
+impl<T> UtVec<T> {
+    /// Give this vector an owner, so that it can be indexed with [`UtIndex`]s
+    ///
+    /// This is safe because there are no [`UtIndex`]s for an unbranded [`UtVec`], so
+    /// there are no old indices which could be out of bounds of the branded [`UtVec`].
+    #[inline]
+    pub fn brand<O: UniqueToken>(self, owner: O) -> UtVec<T, O> {
+        UtVec::from_parts(self.data, owner)
+    }
+
+    /// Brand this vector with a fresh lifetime for the duration of the closure
+    ///
+    /// This is an easy way to migrate code from [`Vec`] and [`usize`] indices to
+    /// [`UtIndex`]s, one piece at a time.
+    ///
+    /// ```
+    /// use ut_vec::UtVec;
+    ///
+    /// // an id system based on `Vec` and `usize`
+    /// let names = vec!["alice", "bob", "carol"];
+    ///
+    /// let total_len = UtVec::from_vec(names).with_brand(|names| {
+    ///     // these ids can only be used with this `names`, so indexing
+    ///     // doesn't need any bounds checks
+    ///     let ids = names.indices().collect::<Vec<_>>();
+    ///     ids.iter().map(|&id| names[id].len()).sum::<usize>()
+    /// });
+    ///
+    /// assert_eq!(total_len, 13);
+    /// ```
+    pub fn with_brand<R>(self, f: impl for<'id> FnOnce(BrandedVec<'id, T>) -> R) -> R {
+        unique_types::lifetime::LifetimeUt::with(|owner| f(self.brand(owner)))
+    }
+}
+
 impl<T, O: UniqueToken> UtVec<T, O> {
     /// Create an empty [`UtVec`] with the given owner
     #[inline]
@@ -124,6 +385,40 @@ impl<T, O> UtVec<T, O> {
     pub fn into_vec(self) -> Vec<T> {
         self.data
     }
+
+    /// Split the vector at `mid`, and give each half a new owner
+    ///
+    /// The first half keeps the original allocation, and the second half is moved into a new
+    /// allocation (see [`Vec::split_off`]). Since both halves have new owners, indices into
+    /// this vector can't be used with either half, and each half gives out it's own indices.
+    ///
+    /// # Panics
+    ///
+    /// If `mid > len`
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let vec = UtVec::from_vec(vec![1, 2, 3, 4, 5]);
+    /// let (left, right) = vec.split_rebrand(2, RuntimeUt::new(), RuntimeUt::new());
+    ///
+    /// let last = right.last_indexed().unwrap().0;
+    /// assert_eq!(left.as_slice(), [1, 2]);
+    /// assert_eq!(right[last], 5);
+    /// assert!(left.get(last).is_none());
+    /// ```
+    #[cfg(feature = "unique-types")]
+    pub fn split_rebrand<O1: UniqueToken, O2: UniqueToken>(
+        self,
+        mid: usize,
+        o1: O1,
+        o2: O2,
+    ) -> (UtVec<T, O1>, UtVec<T, O2>) {
+        let mut data = self.data;
+        let tail = data.split_off(mid);
+        (UtVec::from_parts(data, o1), UtVec::from_parts(tail, o2))
+    }
 }
 
 impl<T, O: ?Sized> UtVec<T, O> {
@@ -136,11 +431,68 @@ impl<T, O: ?Sized> UtVec<T, O> {
         &mut self.data
     }
 
+    /// Split the vector into two at the given index, returning the suffix
+    ///
+    /// see [`Vec::split_off`]
+    ///
+    /// # Safety
+    ///
+    /// Any indices into this vector which are greater than or equal to `at` must not be
+    /// used with this vector again, unless the vector has grown back past that index.
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::from_vec(vec![1, 2, 3, 4]);
+    /// // SAFETY: the () owner doesn't have any unchecked indices
+    /// let tail = unsafe { vec.split_off_unchecked(3) };
+    /// assert_eq!(vec.as_slice(), [1, 2, 3]);
+    /// assert_eq!(tail, [4]);
+    /// ```
+    pub unsafe fn split_off_unchecked(&mut self, at: usize) -> Vec<T> {
+        self.data.split_off(at)
+    }
+
+    /// Clone all elements which match the predicate into a new [`UtVec`] with the given owner
+    ///
+    /// This always allocates a new vector. Since the new vector has a different owner,
+    /// indices into this vector can't accidentally be used with the new vector.
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let vec = UtVec::from_vec(vec![1, 2, 3, 4, 5]);
+    /// let evens = vec.filter_into(RuntimeUt::new(), |x| x % 2 == 0);
+    /// assert_eq!(evens.as_slice(), [2, 4]);
+    /// ```
+    #[cfg(feature = "unique-types")]
+    pub fn filter_into<O2: UniqueToken>(
+        &self,
+        owner: O2,
+        mut pred: impl FnMut(&T) -> bool,
+    ) -> UtVec<T, O2>
+    where
+        T: Clone,
+    {
+        let data = self.data.iter().filter(|x| pred(x)).cloned().collect();
+        UtVec::from_parts(data, owner)
+    }
+
     /// see [`Vec::as_slice`]
     pub fn owner(&self) -> &O {
         &self.owner
     }
 
+    /// Get a mutable reference to the owner, for example to use it as a `CellOwner` in `ut-cell`
+    ///
+    /// # Safety
+    ///
+    /// The owner must not be replaced, for example with [`core::mem::swap`], since
+    /// that would let indices from another [`UtVec`] be used with this one. Any other state
+    /// in the owner may be mutated.
+    pub unsafe fn owner_mut(&mut self) -> &mut O {
+        &mut self.owner
+    }
+
     /// see [`Vec::as_slice`]
     pub fn as_slice(&self) -> &[T] {
         &self.data
@@ -156,6 +508,20 @@ impl<T, O: ?Sized> UtVec<T, O> {
         (&mut self.data, &self.owner)
     }
 
+    /// see [`slice::as_ptr_range`]
+    ///
+    /// The pointers are only valid until the [`UtVec`] is mutated
+    pub fn as_ptr_range(&self) -> ops::Range<*const T> {
+        self.data.as_ptr_range()
+    }
+
+    /// see [`slice::as_mut_ptr_range`]
+    ///
+    /// The pointers are only valid until the [`UtVec`] is mutated
+    pub fn as_mut_ptr_range(&mut self) -> ops::Range<*mut T> {
+        self.data.as_mut_ptr_range()
+    }
+
     /// see [`Vec::len`]
     pub fn len(&self) -> usize {
         self.data.len()
@@ -195,6 +561,44 @@ impl<T, O: ?Sized> UtVec<T, O> {
         self.data.push(value)
     }
 
+    /// Push a value, or return it along with the error if allocation fails
+    ///
+    /// see [`UtVec::push`] and [`UtVec::try_reserve`]
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::new();
+    /// assert!(vec.try_push(1).is_ok());
+    /// assert_eq!(vec.as_slice(), [1]);
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), (T, TryReserveError)> {
+        if let Err(err) = self.try_reserve(1) {
+            return Err((value, err));
+        }
+
+        self.data.push(value);
+        Ok(())
+    }
+
+    /// Push a value only if it fits in the current capacity, returning it's index
+    ///
+    /// If there is no spare capacity, this returns the value back instead of reallocating.
+    /// see [`UtVec::push_within_capacity_indexed`] to get a [`UtIndex`] instead
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::from_vec(Vec::with_capacity(1));
+    /// assert_eq!(vec.push_within_capacity('a'), Ok(0));
+    /// assert_eq!(vec.push_within_capacity('b'), Err('b'));
+    /// ```
+    pub fn push_within_capacity(&mut self, value: T) -> Result<usize, T> {
+        if self.len() == self.capacity() {
+            return Err(value);
+        }
+
+        let index = self.len();
+        self.data.push(value);
+        Ok(index)
+    }
+
     /// see [`Vec::append`]
     pub fn append(&mut self, vec: &mut Vec<T>) {
         self.data.append(vec)
@@ -219,6 +623,32 @@ impl<T, O: ?Sized> UtVec<T, O> {
         self.data.resize_with(self.len() + additional, make_value);
     }
 
+    /// Add new elements until the vector is `target_len` long, by calling `make_value`
+    /// with the index of each new element
+    ///
+    /// Returns the range of indices that were added, which is empty if the vector
+    /// was already at least `target_len` long.
+    ///
+    /// ```
+    /// let mut squares = ut_vec::UtVec::from_vec(vec![0]);
+    /// let added = squares.grow_with_index(5, |i| i * i);
+    /// assert_eq!(added, 1..5);
+    /// assert_eq!(squares.as_slice(), [0, 1, 4, 9, 16]);
+    ///
+    /// assert_eq!(squares.grow_with_index(2, |i| i), 5..5);
+    /// ```
+    pub fn grow_with_index(
+        &mut self,
+        target_len: usize,
+        mut make_value: impl FnMut(usize) -> T,
+    ) -> core::ops::Range<usize> {
+        let start = self.len();
+        let end = start.max(target_len);
+        self.reserve(end - start);
+        self.data.extend((start..end).map(&mut make_value));
+        start..end
+    }
+
     /// see [`Vec::extend_from_slice`]
     pub fn extend_from_slice(&mut self, slice: &[T])
     where
@@ -292,15 +722,390 @@ impl<T, O: ?Sized> UtVec<T, O> {
             None
         }
     }
+
+    /// Get a reference to the element(s) at `index`, or the reason `index` is invalid
+    ///
+    /// see [`UtVec::get`]
+    ///
+    /// ```
+    /// use ut_vec::{IndexError, UtVec};
+    ///
+    /// let vec = UtVec::from_vec(vec![1, 2, 3]);
+    ///
+    /// assert!(matches!(vec.try_get(1), Ok(2)));
+    /// assert!(matches!(
+    ///     vec.try_get(3),
+    ///     Err(IndexError::NotInBounds { index: 3, len: 3, .. })
+    /// ));
+    /// assert!(matches!(
+    ///     vec.try_get(2..1),
+    ///     Err(IndexError::OutOfOrder { start: 2, end: 1 })
+    /// ));
+    /// ```
+    pub fn try_get<I: UtVecIndex<O>>(
+        &self,
+        index: I,
+    ) -> Result<&GetOutputType<I, O, T>, IndexError> {
+        index.is_in_bounds(self.len(), &self.owner)?;
+        // SAFETY: index.is_in_bounds checks that the index is in bounds, and ranges are well
+        // ordered
+        Ok(unsafe { self.get_unchecked(index) })
+    }
+
+    /// Get a mutable reference to the element(s) at `index`, or the reason `index` is invalid
+    ///
+    /// see [`UtVec::get_mut`]
+    pub fn try_get_mut<I: UtVecIndex<O>>(
+        &mut self,
+        index: I,
+    ) -> Result<&mut GetOutputType<I, O, T>, IndexError> {
+        index.is_in_bounds(self.len(), &self.owner)?;
+        // SAFETY: index.is_in_bounds checks that the index is in bounds, and ranges are well
+        // ordered
+        Ok(unsafe { self.get_unchecked_mut(index) })
+    }
+
+    /// Get mutable references to multiple elements at once
+    ///
+    /// Returns [`None`] if any of the indices are out of bounds, or if any two indices are equal
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::from_vec(vec![1, 2, 3, 4]);
+    ///
+    /// let [a, b] = vec.get_disjoint_mut([3, 0]).unwrap();
+    /// core::mem::swap(a, b);
+    /// assert_eq!(vec.as_slice(), [4, 2, 3, 1]);
+    ///
+    /// assert!(vec.get_disjoint_mut([1, 1]).is_none());
+    /// assert!(vec.get_disjoint_mut([1, 4]).is_none());
+    /// assert_eq!(vec.get_disjoint_mut::<usize, 0>([]), Some([]));
+    /// ```
+    pub fn get_disjoint_mut<I: UtVecElementIndex<O>, const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Option<[&mut T; N]> {
+        let len = self.len();
+
+        for (i, index) in indices.iter().enumerate() {
+            index.is_in_bounds(len, &self.owner).ok()?;
+
+            let index = index.get_index();
+            if indices[..i].iter().any(|other| other.get_index() == index) {
+                return None;
+            }
+        }
+
+        let data = self.data.as_mut_ptr();
+        // SAFETY: all indices are in bounds, and they are pairwise distinct so none of
+        // the mutable references alias
+        Some(indices.map(|index| unsafe { &mut *data.add(index.get_index()) }))
+    }
+
+    /// Swap the elements at indices `a` and `b`
+    ///
+    /// If `a` and `b` are equal, then this does nothing
+    ///
+    /// # Panics
+    ///
+    /// If either index is out of bounds
+    ///
+    /// ```
+    /// let mut vec = ut_vec::UtVec::from_vec(vec![1, 2, 3]);
+    ///
+    /// vec.swap(0, 2);
+    /// assert_eq!(vec.as_slice(), [3, 2, 1]);
+    ///
+    /// vec.swap(1, 1);
+    /// assert_eq!(vec.as_slice(), [3, 2, 1]);
+    /// ```
+    ///
+    /// ```should_panic
+    /// let mut vec = ut_vec::UtVec::from_vec(vec![1, 2, 3]);
+    /// vec.swap(0, 3);
+    /// ```
+    pub fn swap<I: UtVecElementIndex<O>>(&mut self, a: I, b: I) {
+        let len = self.len();
+
+        if let Err(err) = a.is_in_bounds(len, &self.owner) {
+            handle!(err)
+        }
+
+        if let Err(err) = b.is_in_bounds(len, &self.owner) {
+            handle!(err)
+        }
+
+        self.data.swap(a.get_index(), b.get_index())
+    }
 }
 
 #[cfg(feature = "unique-types")]
 impl<T, O: ?Sized + UniqueToken> UtVec<T, O> {
     /// Check if a given index is in bounds, if so return a [`UtIndex`] version of that index
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.extend(0..10);
+    ///
+    /// for i in [0, 1, 5, 9] {
+    ///     assert_eq!(vec.is_in_bounds(i).unwrap().get(), i);
+    ///     assert_eq!(vec.indices().nth(i).unwrap().get(), i);
+    ///     assert_eq!(vec.indices().nth_back(i).unwrap().get(), 9 - i);
+    /// }
+    ///
+    /// assert!(vec.is_in_bounds(10).is_none());
+    /// assert!(vec.indices().nth_back(10).is_none());
+    ///
+    /// let mut indices = vec.indices();
+    /// assert_eq!(indices.nth(2).unwrap().get(), 2);
+    /// assert_eq!(indices.nth_back(2).unwrap().get(), 7);
+    /// assert_eq!(indices.map(|i| i.get()).collect::<Vec<_>>(), [3, 4, 5, 6]);
+    /// ```
     pub fn is_in_bounds(&self, i: usize) -> Option<UtIndex<O>> {
         self.indices().nth(i)
     }
 
+    /// Get the first element and it's [`UtIndex`], or [`None`] if the vector is empty
+    pub fn first_indexed(&self) -> Option<(UtIndex<O>, &T)> {
+        let value = self.data.first()?;
+        // SAFETY: the vector isn't empty, so 0 is in bounds
+        Some((unsafe { UtIndex::new_unchecked(0, &self.owner) }, value))
+    }
+
+    /// Get the last element and it's [`UtIndex`], or [`None`] if the vector is empty
+    pub fn last_indexed(&self) -> Option<(UtIndex<O>, &T)> {
+        let value = self.data.last()?;
+        // SAFETY: the vector isn't empty, so len - 1 is in bounds
+        Some((
+            unsafe { UtIndex::new_unchecked(self.len() - 1, &self.owner) },
+            value,
+        ))
+    }
+
+    /// Get the [`UtIndex`] of the first element, or [`None`] if the vector is empty
+    ///
+    /// ```
+    /// use unique_types::lifetime::LifetimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// LifetimeUt::with(|owner| {
+    ///     let mut vec = UtVec::from_owner(owner);
+    ///     assert!(vec.first_index().is_none());
+    ///     assert!(vec.last_index().is_none());
+    ///
+    ///     vec.extend(["a", "b", "c"]);
+    ///     let first = vec.first_index().unwrap();
+    ///     let last = vec.last_index().unwrap();
+    ///     assert_eq!(vec[first], "a");
+    ///     assert_eq!(vec[last], "c");
+    ///     assert_eq!(vec.first(), Some(&vec[first]));
+    ///     assert_eq!(vec.last(), Some(&vec[last]));
+    /// });
+    /// ```
+    pub fn first_index(&self) -> Option<UtIndex<O>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // SAFETY: the vector isn't empty, so 0 is in bounds
+        Some(unsafe { UtIndex::new_unchecked(0, &self.owner) })
+    }
+
+    /// Get the [`UtIndex`] of the last element, or [`None`] if the vector is empty
+    pub fn last_index(&self) -> Option<UtIndex<O>> {
+        let last = self.len().checked_sub(1)?;
+        // SAFETY: the vector isn't empty, so len - 1 is in bounds
+        Some(unsafe { UtIndex::new_unchecked(last, &self.owner) })
+    }
+
+    /// Push a value and return it's [`UtIndex`]
+    ///
+    /// ```
+    /// use unique_types::lifetime::LifetimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// LifetimeUt::with(|owner| {
+    ///     let mut nodes = UtVec::from_owner(owner);
+    ///     let a = nodes.push_indexed("a");
+    ///     let b = nodes.push_indexed("b");
+    ///     assert_eq!(nodes[a], "a");
+    ///     assert_eq!(nodes[b], "b");
+    ///     assert_eq!(b.get(), 1);
+    /// });
+    /// ```
+    pub fn push_indexed(&mut self, value: T) -> UtIndex<O> {
+        let index = self.len();
+        self.data.push(value);
+        // SAFETY: we just pushed a value at index, so it's in bounds
+        unsafe { UtIndex::new_unchecked(index, &self.owner) }
+    }
+
+    /// Extend the vector, and return the range of [`UtIndex`]s of the new elements
+    ///
+    /// Returns None if no elements were added. This returns an inclusive range, since a
+    /// [`UtIndex`] to one past the end would not be in bounds, and indexing with it would be
+    /// unsound.
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.push(0);
+    ///
+    /// let added = vec.extend_indices((1..).take_while(|&x| x < 4)).unwrap();
+    /// assert_eq!(added.start().get(), 1);
+    /// assert_eq!(vec[added], [1, 2, 3]);
+    ///
+    /// assert!(vec.extend_indices([]).is_none());
+    /// ```
+    pub fn extend_indices<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Option<ops::RangeInclusive<UtIndex<O>>> {
+        let start = self.len();
+        self.data.extend(iter);
+        let last = self.len().checked_sub(1).filter(|&last| last >= start)?;
+
+        // SAFETY: start <= last < self.len(), so both are in bounds
+        unsafe {
+            Some(
+                UtIndex::new_unchecked(start, &self.owner)
+                    ..=UtIndex::new_unchecked(last, &self.owner),
+            )
+        }
+    }
+
+    /// Push a value only if it fits in the current capacity, returning it's [`UtIndex`]
+    ///
+    /// If there is no spare capacity, this returns the value back instead of reallocating
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.reserve_exact(1);
+    ///
+    /// let a = vec.push_within_capacity_indexed('a').unwrap();
+    /// assert_eq!(vec[a], 'a');
+    /// assert!(vec.push_within_capacity_indexed('b').is_err());
+    /// ```
+    pub fn push_within_capacity_indexed(&mut self, value: T) -> Result<UtIndex<O>, T> {
+        let index = self.push_within_capacity(value)?;
+        // SAFETY: we just pushed a value at index, so it's in bounds
+        Ok(unsafe { UtIndex::new_unchecked(index, &self.owner) })
+    }
+
+    /// Binary search this vector for a given element, and return it's [`UtIndex`] if found
+    ///
+    /// Otherwise, returns the index where the element could be inserted while
+    /// maintaining sorted order
+    ///
+    /// see [`slice::binary_search`]
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.extend([1, 3, 5, 7]);
+    ///
+    /// let found = vec.binary_search(&5).unwrap();
+    /// assert_eq!(vec[found], 5);
+    /// assert_eq!(vec.binary_search(&4).err(), Some(2));
+    ///
+    /// let found = vec.binary_search_by_key(&14, |&x| x * 2).unwrap();
+    /// assert_eq!(vec[found], 7);
+    /// ```
+    pub fn binary_search(&self, x: &T) -> Result<UtIndex<O>, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|y| y.cmp(x))
+    }
+
+    /// Binary search this vector with a comparator function
+    ///
+    /// see [`UtVec::binary_search`] and [`slice::binary_search_by`]
+    pub fn binary_search_by(
+        &self,
+        f: impl FnMut(&T) -> core::cmp::Ordering,
+    ) -> Result<UtIndex<O>, usize> {
+        let index = self.data.binary_search_by(f)?;
+        // SAFETY: binary_search_by only returns Ok with an in bounds index
+        Ok(unsafe { UtIndex::new_unchecked(index, &self.owner) })
+    }
+
+    /// Binary search this vector with a key extraction function
+    ///
+    /// see [`UtVec::binary_search`] and [`slice::binary_search_by_key`]
+    pub fn binary_search_by_key<B: Ord>(
+        &self,
+        b: &B,
+        mut f: impl FnMut(&T) -> B,
+    ) -> Result<UtIndex<O>, usize> {
+        self.binary_search_by(|x| f(x).cmp(b))
+    }
+
+    /// Find the first element which matches the predicate, and return it's [`UtIndex`]
+    ///
+    /// see [`Iterator::position`]
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.extend([1, 2, 3, 4]);
+    ///
+    /// let found = vec.find_index(|&x| x > 2).unwrap();
+    /// assert_eq!(found.get(), 2);
+    /// assert_eq!(vec[found], 3);
+    ///
+    /// assert!(vec.find_index(|&x| x > 4).is_none());
+    /// ```
+    pub fn find_index(&self, pred: impl FnMut(&T) -> bool) -> Option<UtIndex<O>> {
+        let index = self.data.iter().position(pred)?;
+        // SAFETY: position only returns in bounds indices
+        Some(unsafe { UtIndex::new_unchecked(index, &self.owner) })
+    }
+
+    /// An iterator over all elements and their [`UtIndex`]s
+    ///
+    /// ```
+    /// use unique_types::runtime::RuntimeUt;
+    /// use ut_vec::UtVec;
+    ///
+    /// let mut vec = UtVec::from_owner(RuntimeUt::new());
+    /// vec.extend(['a', 'b', 'c']);
+    ///
+    /// let indices = vec.iter_indexed().map(|(index, _)| index).collect::<Vec<_>>();
+    /// for (index, value) in vec.iter_indexed_mut() {
+    ///     *value = value.to_ascii_uppercase();
+    /// }
+    ///
+    /// assert_eq!(vec[indices[1]], 'B');
+    /// ```
+    pub fn iter_indexed(&self) -> IterIndexed<'_, T, O> {
+        IterIndexed {
+            token: self.owner.token(),
+            iter: self.data.iter().enumerate(),
+        }
+    }
+
+    /// An iterator over mutable references to all elements and their [`UtIndex`]s
+    ///
+    /// see [`UtVec::iter_indexed`]
+    pub fn iter_indexed_mut(&mut self) -> IterIndexedMut<'_, T, O> {
+        IterIndexedMut {
+            token: self.owner.token(),
+            iter: self.data.iter_mut().enumerate(),
+        }
+    }
+
     /// An iterator over all valid indices in this vector
     pub fn indices(&self) -> Indices<O> {
         Indices {
@@ -346,6 +1151,7 @@ impl<O: UniqueToken + ?Sized> Iterator for Indices<O> {
             self.start = self.end;
             None
         } else {
+            self.start = start;
             self.next()
         }
     }
@@ -381,11 +1187,77 @@ impl<O: UniqueToken + ?Sized> DoubleEndedIterator for Indices<O> {
             self.start = self.end;
             None
         } else {
+            self.end = end;
             self.next_back()
         }
     }
 }
 
+#[cfg(feature = "unique-types")]
+/// An iterator over all elements and their indices in a [`UtVec`],
+/// created from [`UtVec::iter_indexed`]
+pub struct IterIndexed<'a, T, O: ?Sized + UniqueToken> {
+    token: O::Token,
+    iter: core::iter::Enumerate<core::slice::Iter<'a, T>>,
+}
+
+#[cfg(feature = "unique-types")]
+/// An iterator over mutable references to all elements and their indices in a [`UtVec`],
+/// created from [`UtVec::iter_indexed_mut`]
+pub struct IterIndexedMut<'a, T, O: ?Sized + UniqueToken> {
+    token: O::Token,
+    iter: core::iter::Enumerate<core::slice::IterMut<'a, T>>,
+}
+
+macro_rules! iter_indexed_impl {
+    ($name:ident $item:ty) => {
+        #[cfg(feature = "unique-types")]
+        impl<'a, T, O: UniqueToken + ?Sized> Iterator for $name<'a, T, O> {
+            type Item = (UtIndex<O>, $item);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let (index, value) = self.iter.next()?;
+                // SAFETY: the index comes from iterating over the vector, so it's in bounds
+                let index = unsafe { UtIndex::from_token_unchecked(index, self.token) };
+                Some((index, value))
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                let (index, value) = self.iter.nth(n)?;
+                // SAFETY: the index comes from iterating over the vector, so it's in bounds
+                let index = unsafe { UtIndex::from_token_unchecked(index, self.token) };
+                Some((index, value))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        #[cfg(feature = "unique-types")]
+        impl<T, O: UniqueToken + ?Sized> DoubleEndedIterator for $name<'_, T, O> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let (index, value) = self.iter.next_back()?;
+                // SAFETY: the index comes from iterating over the vector, so it's in bounds
+                let index = unsafe { UtIndex::from_token_unchecked(index, self.token) };
+                Some((index, value))
+            }
+        }
+
+        #[cfg(feature = "unique-types")]
+        impl<T, O: UniqueToken + ?Sized> ExactSizeIterator for $name<'_, T, O> {}
+        #[cfg(feature = "unique-types")]
+        impl<T, O: UniqueToken + ?Sized> core::iter::FusedIterator for $name<'_, T, O> {}
+    };
+}
+
+iter_indexed_impl!(IterIndexed &'a T);
+iter_indexed_impl!(IterIndexedMut &'a mut T);
+
 impl<T, A, O> Extend<A> for UtVec<T, O>
 where
     Vec<T>: Extend<A>,